@@ -0,0 +1,89 @@
+//! Benchmarks every registered day on its sample input (`data/samples`) and
+//! on the real input (`data`). Pass day numbers to benchmark only those days,
+//! e.g. `cargo bench -- 6 9`.
+
+extern crate solutions_lib;
+
+use std::env;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use solutions_lib::day::format_duration;
+use solutions_lib::utils::InputSource;
+use solutions_lib::{DaySolver, DAYS};
+
+/// Every input runs at least `MIN_RUNS` times and then until `TARGET_TIME`
+/// has passed or `MAX_RUNS` is reached.
+const MIN_RUNS: u32 = 3;
+const MAX_RUNS: u32 = 1000;
+const TARGET_TIME: Duration = Duration::from_secs(1);
+
+/// Mean time of each step over all runs of one input.
+struct Measurement {
+    runs: u32,
+    parse: Duration,
+    part1: Duration,
+    part2: Duration,
+}
+
+fn measure(solver: &DaySolver, input: &str) -> Result<Measurement, String> {
+    let mut total = Measurement { runs: 0, parse: Duration::ZERO, part1: Duration::ZERO, part2: Duration::ZERO };
+    let start = Instant::now();
+
+    while total.runs < MIN_RUNS || (start.elapsed() < TARGET_TIME && total.runs < MAX_RUNS) {
+        let (outcome, timings) = (solver.run)(&mut input.as_bytes(), None)
+            .map_err(|err| err.to_string())?;
+        if let Some((part, err)) = outcome.errors().first() {
+            return Err(format!("part {}: {}", part, err));
+        }
+        total.runs += 1;
+        total.parse += timings.parse;
+        total.part1 += timings.part1.unwrap_or_default();
+        total.part2 += timings.part2.unwrap_or_default();
+    }
+
+    Ok(Measurement {
+        runs: total.runs,
+        parse: total.parse / total.runs,
+        part1: total.part1 / total.runs,
+        part2: total.part2 / total.runs,
+    })
+}
+
+fn bench(solver: &DaySolver, kind: &str, source: &InputSource) {
+    let input = match solver.input {
+        Some(file_name) => match source.read(file_name) {
+            Ok(input) => input,
+            Err(_) => {
+                println!("day {:>2} {:<6}  skipped, no input", solver.day, kind);
+                return;
+            }
+        },
+        None => String::new(),
+    };
+
+    match measure(solver, &input) {
+        Ok(m) => println!("day {:>2} {:<6}  parse {:>10}  part 1 {:>10}  part 2 {:>10}  ({} runs)",
+                          solver.day, kind, format_duration(m.parse), format_duration(m.part1),
+                          format_duration(m.part2), m.runs),
+        Err(err) => println!("day {:>2} {:<6}  failed: {}", solver.day, kind, err),
+    }
+}
+
+fn main() {
+    // cargo passes flags like --bench, everything else selects days
+    let days: Vec<u32> = env::args()
+        .skip(1)
+        .filter(|arg| !arg.starts_with('-'))
+        .filter_map(|arg| arg.parse().ok())
+        .collect();
+
+    let data = Path::new(env!("CARGO_MANIFEST_DIR")).join("data");
+    let samples = InputSource::Dir(data.join("samples"));
+    let real = InputSource::Dir(data);
+
+    for solver in DAYS.iter().filter(|solver| days.is_empty() || days.contains(&solver.day)) {
+        bench(solver, "sample", &samples);
+        bench(solver, "real", &real);
+    }
+}
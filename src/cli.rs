@@ -0,0 +1,318 @@
+use std::fmt;
+use std::path::PathBuf;
+
+use solutions_lib::Part;
+use solutions_lib::advent1;
+use solutions_lib::utils::InputSource;
+
+pub static USAGE: &str = "\
+Usage:
+    aoc_2018 run --day <N> [--part <1|2>] [--input <FILE|->] [--inputs-dir <DIR>]
+    aoc_2018 run --all [--part <1|2>] [--inputs-dir <DIR>]
+    aoc_2018 run (--day <N> | --all) [--part <1|2>] (--profile <NAME> | --all-profiles)
+    aoc_2018 drift [--passes <N>] [--input <FILE|-> | --inputs-dir <DIR>]
+    aoc_2018 new <N>
+    aoc_2018 help
+
+Inputs are read from the inputs directory ('data' or $AOC_INPUTS_DIR) by default.
+'--input -' reads the input of a single day from stdin.
+Profiles are subdirectories of the inputs directory with their own answers.toml,
+the inputs directory itself is the profile 'default'. Runs with a profile report
+every answer that disagrees with the profile's answers.
+'drift' prints the frequency drift report of day 1 as key=value lines, for N passes
+(at most 100000) or up to the pass in which the first frequency repeats.
+'new' creates and registers the solution of day N from src/template.rs.";
+
+#[derive(Debug, PartialEq)]
+pub enum DaySelection {
+    Single(u32),
+    All,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ProfileSelection {
+    Named(String),
+    All,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    /// `input` is `None` if the input should be found via the environment.
+    /// Runs with a profile read the inputs of the profile and check the answers.
+    Run { days: DaySelection, part: Option<Part>, input: Option<InputSource>,
+          profile: Option<ProfileSelection> },
+    /// `passes` is `None` to report up to the first repeated frequency.
+    Drift { input: Option<InputSource>, passes: Option<usize> },
+    New { day: u32 },
+    Help,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ArgError {
+    MissingCommand,
+    UnknownCommand(String),
+    UnknownFlag(String),
+    UnexpectedArgument(String),
+    MissingValue(&'static str),
+    InvalidValue(&'static str, String),
+    MissingDay,
+    MissingNewDay,
+    ConflictingDays,
+    ConflictingInputs,
+    ConflictingProfiles,
+    InputForAllDays,
+}
+
+impl fmt::Display for ArgError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ArgError::MissingCommand => write!(f, "no command given"),
+            ArgError::UnknownCommand(cmd) => write!(f, "unknown command '{}'", cmd),
+            ArgError::UnknownFlag(flag) => write!(f, "unknown flag '{}'", flag),
+            ArgError::UnexpectedArgument(arg) => write!(f, "unexpected argument '{}'", arg),
+            ArgError::MissingValue(flag) => write!(f, "flag '{}' needs a value", flag),
+            ArgError::InvalidValue(flag, value) =>
+                write!(f, "invalid value '{}' for flag '{}'", value, flag),
+            ArgError::MissingDay => write!(f, "select a day with '--day <N>' or use '--all'"),
+            ArgError::MissingNewDay => write!(f, "'new' needs the number of the day to create"),
+            ArgError::ConflictingDays => write!(f, "'--day' and '--all' can't be used together"),
+            ArgError::ConflictingInputs =>
+                write!(f, "only one of '--input', '--inputs-dir' and the profile flags can be used"),
+            ArgError::ConflictingProfiles =>
+                write!(f, "'--profile' and '--all-profiles' can't be used together"),
+            ArgError::InputForAllDays =>
+                write!(f, "'--input' selects the input of one day, use '--inputs-dir' with '--all'"),
+        }
+    }
+}
+
+fn parse_part(value: &str) -> Result<Part, ArgError> {
+    match value {
+        "1" => Ok(Part::One),
+        "2" => Ok(Part::Two),
+        _ => Err(ArgError::InvalidValue("--part", value.to_owned())),
+    }
+}
+
+fn parse_run<I: Iterator<Item=String>>(mut args: I) -> Result<Command, ArgError> {
+    let mut day = None;
+    let mut all = false;
+    let mut part = None;
+    let mut input = None;
+    let mut profile = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" | "-d" => {
+                let value = args.next().ok_or(ArgError::MissingValue("--day"))?;
+                let parsed = value.parse::<u32>()
+                    .map_err(|_| ArgError::InvalidValue("--day", value.clone()))?;
+                day = Some(parsed);
+            }
+            "--part" | "-p" => {
+                let value = args.next().ok_or(ArgError::MissingValue("--part"))?;
+                part = Some(parse_part(&value)?);
+            }
+            "--input" | "-i" => {
+                let value = args.next().ok_or(ArgError::MissingValue("--input"))?;
+                let source = if value == "-" {
+                    InputSource::Stdin
+                } else {
+                    InputSource::File(PathBuf::from(value))
+                };
+
+                if input.is_some() {
+                    return Err(ArgError::ConflictingInputs);
+                }
+                input = Some(source);
+            }
+            "--inputs-dir" => {
+                let value = args.next().ok_or(ArgError::MissingValue("--inputs-dir"))?;
+                if input.is_some() {
+                    return Err(ArgError::ConflictingInputs);
+                }
+                input = Some(InputSource::Dir(PathBuf::from(value)));
+            }
+            "--profile" => {
+                let value = args.next().ok_or(ArgError::MissingValue("--profile"))?;
+                if profile.is_some() {
+                    return Err(ArgError::ConflictingProfiles);
+                }
+                profile = Some(ProfileSelection::Named(value));
+            }
+            "--all-profiles" => {
+                if profile.is_some() {
+                    return Err(ArgError::ConflictingProfiles);
+                }
+                profile = Some(ProfileSelection::All);
+            }
+            "--all" | "-a" => all = true,
+            _ => return Err(ArgError::UnknownFlag(arg)),
+        }
+    }
+
+    let days = match (day, all) {
+        (Some(_), true) => return Err(ArgError::ConflictingDays),
+        (Some(day), false) => DaySelection::Single(day),
+        (None, true) => DaySelection::All,
+        (None, false) => return Err(ArgError::MissingDay),
+    };
+
+    match input {
+        Some(_) if profile.is_some() => Err(ArgError::ConflictingInputs),
+        Some(InputSource::File(_)) | Some(InputSource::Stdin) if days == DaySelection::All =>
+            Err(ArgError::InputForAllDays),
+        _ => Ok(Command::Run { days, part, input, profile }),
+    }
+}
+
+fn parse_drift<I: Iterator<Item=String>>(mut args: I) -> Result<Command, ArgError> {
+    let mut input = None;
+    let mut passes = None;
+
+    while let Some(arg) = args.next() {
+        let source = match arg.as_str() {
+            "--passes" => {
+                let value = args.next().ok_or(ArgError::MissingValue("--passes"))?;
+                let parsed = value.parse::<usize>().ok()
+                    .filter(|&passes| passes <= advent1::MAX_REPORT_PASSES)
+                    .ok_or_else(|| ArgError::InvalidValue("--passes", value.clone()))?;
+                passes = Some(parsed);
+                continue;
+            }
+            "--input" | "-i" => match args.next().ok_or(ArgError::MissingValue("--input"))? {
+                ref value if value == "-" => InputSource::Stdin,
+                value => InputSource::File(PathBuf::from(value)),
+            },
+            "--inputs-dir" => {
+                let value = args.next().ok_or(ArgError::MissingValue("--inputs-dir"))?;
+                InputSource::Dir(PathBuf::from(value))
+            }
+            _ => return Err(ArgError::UnknownFlag(arg)),
+        };
+
+        if input.is_some() {
+            return Err(ArgError::ConflictingInputs);
+        }
+        input = Some(source);
+    }
+
+    Ok(Command::Drift { input, passes })
+}
+
+fn parse_new<I: Iterator<Item=String>>(mut args: I) -> Result<Command, ArgError> {
+    let value = args.next().ok_or(ArgError::MissingNewDay)?;
+    let day = value.parse::<u32>()
+        .map_err(|_| ArgError::InvalidValue("new", value.clone()))?;
+
+    match args.next() {
+        Some(arg) => Err(ArgError::UnexpectedArgument(arg)),
+        None => Ok(Command::New { day }),
+    }
+}
+
+/// Parse the command line arguments (without the program name).
+pub fn parse_args<I: Iterator<Item=String>>(mut args: I) -> Result<Command, ArgError> {
+    match args.next() {
+        Some(ref cmd) if cmd == "run" => parse_run(args),
+        Some(ref cmd) if cmd == "new" => parse_new(args),
+        Some(ref cmd) if cmd == "drift" => parse_drift(args),
+        Some(ref cmd) if cmd == "help" || cmd == "--help" || cmd == "-h" => Ok(Command::Help),
+        Some(cmd) => Err(ArgError::UnknownCommand(cmd)),
+        None => Err(ArgError::MissingCommand),
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Command, ArgError> {
+        parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_parse_run() {
+        assert_eq!(parse(&["run", "--day", "3", "--part", "2"]),
+                   Ok(Command::Run { days: DaySelection::Single(3), part: Some(Part::Two),
+                                     input: None, profile: None }));
+        assert_eq!(parse(&["run", "--all"]),
+                   Ok(Command::Run { days: DaySelection::All, part: None, input: None, profile: None }));
+        assert_eq!(parse(&["run", "-d", "9"]),
+                   Ok(Command::Run { days: DaySelection::Single(9), part: None, input: None, profile: None }));
+    }
+
+    #[test]
+    fn test_parse_inputs() {
+        assert_eq!(parse(&["run", "--day", "3", "--input", "my_input"]),
+                   Ok(Command::Run { days: DaySelection::Single(3), part: None,
+                                     input: Some(InputSource::File(PathBuf::from("my_input"))),
+                                     profile: None }));
+        assert_eq!(parse(&["run", "--day", "3", "--input", "-"]),
+                   Ok(Command::Run { days: DaySelection::Single(3), part: None,
+                                     input: Some(InputSource::Stdin), profile: None }));
+        assert_eq!(parse(&["run", "--all", "--inputs-dir", "inputs"]),
+                   Ok(Command::Run { days: DaySelection::All, part: None,
+                                     input: Some(InputSource::Dir(PathBuf::from("inputs"))),
+                                     profile: None }));
+        assert_eq!(parse(&["run", "--all", "--input", "-"]), Err(ArgError::InputForAllDays));
+        assert_eq!(parse(&["run", "--day", "1", "--input", "a", "--inputs-dir", "b"]),
+                   Err(ArgError::ConflictingInputs));
+    }
+
+    #[test]
+    fn test_parse_profiles() {
+        assert_eq!(parse(&["run", "--all", "--profile", "alice"]),
+                   Ok(Command::Run { days: DaySelection::All, part: None, input: None,
+                                     profile: Some(ProfileSelection::Named("alice".to_owned())) }));
+        assert_eq!(parse(&["run", "--day", "9", "--all-profiles"]),
+                   Ok(Command::Run { days: DaySelection::Single(9), part: None, input: None,
+                                     profile: Some(ProfileSelection::All) }));
+        assert_eq!(parse(&["run", "--all", "--profile"]), Err(ArgError::MissingValue("--profile")));
+        assert_eq!(parse(&["run", "--all", "--profile", "a", "--all-profiles"]),
+                   Err(ArgError::ConflictingProfiles));
+        assert_eq!(parse(&["run", "--all", "--profile", "a", "--inputs-dir", "b"]),
+                   Err(ArgError::ConflictingInputs));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(parse(&[]), Err(ArgError::MissingCommand));
+        assert_eq!(parse(&["solve"]), Err(ArgError::UnknownCommand("solve".to_owned())));
+        assert_eq!(parse(&["run"]), Err(ArgError::MissingDay));
+        assert_eq!(parse(&["run", "--day"]), Err(ArgError::MissingValue("--day")));
+        assert_eq!(parse(&["run", "--day", "x"]),
+                   Err(ArgError::InvalidValue("--day", "x".to_owned())));
+        assert_eq!(parse(&["run", "--day", "1", "--part", "3"]),
+                   Err(ArgError::InvalidValue("--part", "3".to_owned())));
+        assert_eq!(parse(&["run", "--day", "1", "--all"]), Err(ArgError::ConflictingDays));
+        assert_eq!(parse(&["run", "--fast"]), Err(ArgError::UnknownFlag("--fast".to_owned())));
+    }
+
+    #[test]
+    fn test_parse_drift() {
+        assert_eq!(parse(&["drift"]), Ok(Command::Drift { input: None, passes: None }));
+        assert_eq!(parse(&["drift", "--passes", "4", "--input", "-"]),
+                   Ok(Command::Drift { input: Some(InputSource::Stdin), passes: Some(4) }));
+        assert_eq!(parse(&["drift", "--inputs-dir", "inputs"]),
+                   Ok(Command::Drift { input: Some(InputSource::Dir(PathBuf::from("inputs"))),
+                                       passes: None }));
+        assert_eq!(parse(&["drift", "--passes", "many"]),
+                   Err(ArgError::InvalidValue("--passes", "many".to_owned())));
+        assert_eq!(parse(&["drift", "--passes", "100001"]),
+                   Err(ArgError::InvalidValue("--passes", "100001".to_owned())));
+        assert_eq!(parse(&["drift", "--input", "a", "--inputs-dir", "b"]),
+                   Err(ArgError::ConflictingInputs));
+    }
+
+    #[test]
+    fn test_parse_new() {
+        assert_eq!(parse(&["new", "11"]), Ok(Command::New { day: 11 }));
+        assert_eq!(parse(&["new"]), Err(ArgError::MissingNewDay));
+        assert_eq!(parse(&["new", "eleven"]),
+                   Err(ArgError::InvalidValue("new", "eleven".to_owned())));
+        assert_eq!(parse(&["new", "11", "12"]),
+                   Err(ArgError::UnexpectedArgument("12".to_owned())));
+    }
+}
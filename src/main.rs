@@ -1,5 +1,236 @@
 extern crate solutions_lib;
 
+mod cli;
+mod scaffold;
+
+use std::env;
+use std::io::{self, BufRead};
+use std::path::Path;
+use std::process;
+
+use cli::{Command, DaySelection, ProfileSelection};
+use solutions_lib::answers::{compare, Disagreement};
+use solutions_lib::profiles::{self, Profile};
+use solutions_lib::utils::{self, InputSource};
+use solutions_lib::advent1;
+use solutions_lib::{Answers, Day, DaySolver, Error, Part};
+
+/// Multi-line answers (like pictures) start on their own line.
+fn print_answer(part: Part, answer: &str) {
+    if answer.contains('\n') {
+        println!("Part {}:\n{}", part, answer);
+    } else {
+        println!("Part {}: {}", part, answer);
+    }
+}
+
+/// What went wrong in the parts of a day that could be run.
+struct DayReport {
+    /// Answers that differ from the accepted ones.
+    disagreements: Vec<Disagreement>,
+    /// Number of parts that failed, each is reported when it fails.
+    failed_parts: usize,
+}
+
+/// Run a day and print its answers, or the error of every part that fails.
+/// Only errors reading or parsing the input are returned.
+fn run_solver(solver: &DaySolver, part: Option<Part>, source: &InputSource,
+              accepted: Option<&Answers>) -> Result<DayReport, Error> {
+    println!("Day {}", solver.day);
+    println!("==================================================");
+
+    // the input is parsed while it is read
+    let mut input: Box<dyn BufRead> = match solver.input {
+        Some(file_name) => source.open(file_name)?,
+        None => Box::new(io::empty()),
+    };
+    let (outcome, timings) = (solver.run)(&mut input, part)?;
+
+    for (part, answer) in [(Part::One, &outcome.part1), (Part::Two, &outcome.part2)] {
+        match answer {
+            Some(Ok(answer)) => print_answer(part, answer),
+            Some(Err(err)) => eprintln!("error: day {} part {}: {}", solver.day, part, err),
+            None => {}
+        }
+    }
+
+    let answers = outcome.answers();
+    let disagreements = accepted
+        .map_or_else(Vec::new, |accepted| compare(solver.day, accepted, &answers));
+    for disagreement in &disagreements {
+        println!("Disagreement: {}", disagreement);
+    }
+
+    println!("Time: {}", timings);
+    println!();
+    Ok(DayReport { disagreements, failed_parts: outcome.errors().len() })
+}
+
+/// Run all solvers and report failing days without stopping. With
+/// `skip_missing`, days whose input file is missing are skipped.
+/// Returns whether every day that ran was solved.
+fn run_solvers<'a, I>(solvers: I, part: Option<Part>, source: &InputSource, skip_missing: bool) -> bool
+where
+    I: IntoIterator<Item=&'a DaySolver>,
+{
+    let mut all_solved = true;
+    for solver in solvers {
+        let has_input = solver.input.is_none_or(|file_name| source.has_input(file_name));
+        if skip_missing && !has_input {
+            println!("Day {}: no input, skipped\n", solver.day);
+            continue;
+        }
+
+        match run_solver(solver, part, source, None) {
+            Ok(report) => all_solved &= report.failed_parts == 0,
+            Err(err) => {
+                eprintln!("error: day {}: {}\n", solver.day, err);
+                all_solved = false;
+            }
+        }
+    }
+
+    all_solved
+}
+
+/// Run the days on the inputs of a profile and check them against the
+/// profile's answers. Days without input and answers in the profile are
+/// skipped. Returns whether every day was solved with the accepted answers.
+fn run_profile<'a, I>(profile: &Profile, solvers: I, part: Option<Part>) -> bool
+where
+    I: IntoIterator<Item=&'a DaySolver>,
+{
+    println!("Profile {}", profile.name);
+    println!("##################################################");
+    println!();
+
+    let accepted = match profile.answers() {
+        Ok(accepted) => accepted,
+        Err(err) => {
+            eprintln!("error: profile {}: {}\n", profile.name, err);
+            return false;
+        }
+    };
+
+    let source = profile.source();
+    let mut all_solved = true;
+    let mut disagreements = Vec::new();
+    for solver in solvers {
+        let has_input = solver.input.is_none_or(|file_name| profile.has_input(file_name));
+        if !has_input && !accepted.contains_key(&solver.day) {
+            continue;
+        }
+
+        match run_solver(solver, part, &source, accepted.get(&solver.day)) {
+            Ok(report) => {
+                all_solved &= report.failed_parts == 0;
+                disagreements.extend(report.disagreements);
+            }
+            Err(err) => {
+                eprintln!("error: day {}: {}\n", solver.day, err);
+                all_solved = false;
+            }
+        }
+    }
+
+    if disagreements.is_empty() {
+        println!("Profile {}: no disagreements with the accepted answers", profile.name);
+    } else {
+        println!("Profile {}: {} disagreement(s) with the accepted answers", profile.name,
+                 disagreements.len());
+        for disagreement in &disagreements {
+            println!("    {}", disagreement);
+        }
+    }
+    println!();
+
+    all_solved && disagreements.is_empty()
+}
+
+/// Run the days for the selected profiles of the inputs directory.
+fn run_profiles(selection: ProfileSelection, solvers: &[DaySolver], part: Option<Part>) -> bool {
+    let root = utils::inputs_dir();
+    let found = match selection {
+        ProfileSelection::Named(name) => profiles::find_profile(&root, &name).map(|profile| vec![profile]),
+        ProfileSelection::All => profiles::list_profiles(&root),
+    };
+
+    match found {
+        // run every profile, even if an earlier one failed
+        Ok(found) => found.iter()
+            .map(|profile| run_profile(profile, solvers, part))
+            .filter(|&solved| !solved)
+            .count() == 0,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            false
+        }
+    }
+}
+
 fn main() {
-    solutions_lib::advent9::solve_day();
+    let command = match cli::parse_args(env::args().skip(1)) {
+        Ok(command) => command,
+        Err(err) => {
+            eprintln!("error: {}\n\n{}", err, cli::USAGE);
+            process::exit(2);
+        }
+    };
+
+    match command {
+        Command::Help => println!("{}", cli::USAGE),
+        Command::Drift { input, passes } => {
+            let source = input.unwrap_or_else(InputSource::from_env);
+            let report = source.open(advent1::INPUT)
+                .and_then(advent1::Day1::parse_reader)
+                .and_then(|freqs| advent1::drift_report(&freqs, passes));
+            match report {
+                Ok(report) => print!("{}", report),
+                Err(err) => {
+                    eprintln!("error: day 1: {}", err);
+                    process::exit(1);
+                }
+            }
+        }
+        Command::New { day } => {
+            match scaffold::create_day(Path::new(env!("CARGO_MANIFEST_DIR")), day) {
+                Ok(files) => {
+                    for file in files {
+                        println!("wrote {}", file.display());
+                    }
+                }
+                Err(err) => {
+                    eprintln!("error: {}", err);
+                    process::exit(1);
+                }
+            }
+        }
+        Command::Run { days, part, input, profile } => {
+            // running everything skips the days without an input, a
+            // single day without input is an error
+            let skip_missing = days == DaySelection::All;
+            let solvers = match days {
+                DaySelection::All => solutions_lib::DAYS,
+                DaySelection::Single(day) => match solutions_lib::find_day(day) {
+                    Ok(solver) => std::slice::from_ref(solver),
+                    Err(err) => {
+                        eprintln!("error: {}", err);
+                        process::exit(1);
+                    }
+                },
+            };
+
+            let solved = match profile {
+                None => {
+                    let source = input.unwrap_or_else(InputSource::from_env);
+                    run_solvers(solvers, part, &source, skip_missing)
+                }
+                Some(selection) => run_profiles(selection, solvers, part),
+            };
+
+            if !solved {
+                process::exit(1);
+            }
+        }
+    }
 }
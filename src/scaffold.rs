@@ -0,0 +1,211 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use solutions_lib::LAST_DAY;
+
+/// The template every new day starts from.
+static TEMPLATE: &str = include_str!("template.rs");
+
+/// Where the generated files go, relative to the crate root.
+static SOLUTIONS_DIR: &str = "src/solutions";
+static SAMPLES_DIR: &str = "data/samples";
+
+#[derive(Debug)]
+pub enum ScaffoldError {
+    UnknownDay(u32),
+    /// The solution file of the day exists already.
+    AlreadyExists(PathBuf),
+    /// The day is declared in `solutions/mod.rs` already.
+    AlreadyRegistered(u32),
+    /// `solutions/mod.rs` has no place to register the day.
+    MissingRegistry(&'static str),
+    Io(PathBuf, io::Error),
+}
+
+impl fmt::Display for ScaffoldError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScaffoldError::UnknownDay(day) =>
+                write!(f, "day {} does not exist, pick a day from 1 to {}", day, LAST_DAY),
+            ScaffoldError::AlreadyExists(path) =>
+                write!(f, "'{}' exists already, not overwriting it", path.display()),
+            ScaffoldError::AlreadyRegistered(day) =>
+                write!(f, "day {} is registered in solutions/mod.rs already", day),
+            ScaffoldError::MissingRegistry(what) =>
+                write!(f, "could not find the {} in solutions/mod.rs", what),
+            ScaffoldError::Io(path, err) => write!(f, "could not access '{}': {}", path.display(), err),
+        }
+    }
+}
+
+/// The template with the day number filled in.
+pub fn render_template(day: u32) -> String {
+    TEMPLATE
+        .replace("\"input1\"", &format!("\"input{}\"", day))
+        .replace("Day1", &format!("Day{}", day))
+}
+
+/// The number in a line like `prefix42suffix`, if the line has that shape.
+fn numbered_line(line: &str, prefix: &str, suffix: &str) -> Option<u32> {
+    line.trim_end().strip_prefix(prefix)?.strip_suffix(suffix)?.parse().ok()
+}
+
+/// Insert `new_line` among the numbered lines of `lines` so that the
+/// numbers stay sorted. Returns false if there are no numbered lines.
+fn insert_sorted(lines: &mut Vec<String>, new_line: String, day: u32,
+                 number: &dyn Fn(&str) -> Option<u32>) -> bool {
+    let numbered: Vec<(usize, u32)> = lines.iter()
+        .enumerate()
+        .filter_map(|(i, line)| number(line).map(|n| (i, n)))
+        .collect();
+
+    let position = match numbered.iter().rev().find(|&&(_, n)| n < day) {
+        Some(&(i, _)) => i + 1,
+        None => match numbered.first() {
+            Some(&(i, _)) => i,
+            None => return false,
+        },
+    };
+
+    lines.insert(position, new_line);
+    true
+}
+
+/// Add the module declaration and the `DAYS` entry of a day to the
+/// contents of `solutions/mod.rs`.
+pub fn register_day(mod_rs: &str, day: u32) -> Result<String, ScaffoldError> {
+    let module = |line: &str| numbered_line(line, "pub mod advent", ";");
+    let entry = |line: &str| numbered_line(line.split(',').next().unwrap_or(""), "    DaySolver { day: ", "");
+
+    if mod_rs.lines().any(|line| module(line) == Some(day) || entry(line) == Some(day)) {
+        return Err(ScaffoldError::AlreadyRegistered(day));
+    }
+
+    let mut lines: Vec<String> = mod_rs.lines().map(String::from).collect();
+    if !insert_sorted(&mut lines, format!("pub mod advent{};", day), day, &module) {
+        return Err(ScaffoldError::MissingRegistry("'pub mod adventN;' declarations"));
+    }
+
+    let registry_line = format!(
+        "    DaySolver {{ day: {0}, input: Some(advent{0}::INPUT), run: day::run::<advent{0}::Day{0}> }},",
+        day);
+    if !insert_sorted(&mut lines, registry_line.clone(), day, &entry) {
+        // an empty registry gets its first entry right after the opening line
+        let start = lines.iter()
+            .position(|line| line.starts_with("pub static DAYS: &[DaySolver] = &["))
+            .ok_or(ScaffoldError::MissingRegistry("DAYS registry"))?;
+        lines.insert(start + 1, registry_line);
+    }
+
+    let mut registered = lines.join("\n");
+    if mod_rs.ends_with('\n') {
+        registered.push('\n');
+    }
+
+    Ok(registered)
+}
+
+fn write_file(path: &Path, contents: &str) -> Result<(), ScaffoldError> {
+    fs::write(path, contents).map_err(|err| ScaffoldError::Io(path.to_owned(), err))
+}
+
+/// Create the solution file of a day from the template, register it and
+/// add an empty sample input. Nothing is written if the day exists.
+/// Returns the created or changed files.
+pub fn create_day(root: &Path, day: u32) -> Result<Vec<PathBuf>, ScaffoldError> {
+    if !(1..=LAST_DAY).contains(&day) {
+        return Err(ScaffoldError::UnknownDay(day));
+    }
+
+    let solution = root.join(SOLUTIONS_DIR).join(format!("advent{}.rs", day));
+    if solution.exists() {
+        return Err(ScaffoldError::AlreadyExists(solution));
+    }
+
+    let mod_path = root.join(SOLUTIONS_DIR).join("mod.rs");
+    let mod_rs = fs::read_to_string(&mod_path).map_err(|err| ScaffoldError::Io(mod_path.clone(), err))?;
+    let registered = register_day(&mod_rs, day)?;
+
+    write_file(&solution, &render_template(day))?;
+    write_file(&mod_path, &registered)?;
+    let mut created = vec![solution, mod_path];
+
+    // sample inputs are kept if they exist already
+    let samples_dir = root.join(SAMPLES_DIR);
+    let sample = samples_dir.join(format!("input{}", day));
+    if !sample.exists() {
+        fs::create_dir_all(&samples_dir).map_err(|err| ScaffoldError::Io(samples_dir.clone(), err))?;
+        write_file(&sample, "")?;
+        created.push(sample);
+    }
+
+    Ok(created)
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static MOD_RS: &str = "\
+pub mod advent1;
+pub mod advent3;
+
+pub static DAYS: &[DaySolver] = &[
+    DaySolver { day: 1, input: Some(advent1::INPUT), run: day::run::<advent1::Day1> },
+    DaySolver { day: 3, input: Some(advent3::INPUT), run: day::run::<advent3::Day3> },
+];
+";
+
+    #[test]
+    fn test_render_template() {
+        let rendered = render_template(12);
+        assert!(rendered.contains("pub static INPUT: &str = \"input12\";"));
+        assert!(rendered.contains("pub struct Day12;"));
+        assert!(rendered.contains("impl Day for Day12 {"));
+        assert!(!rendered.contains("Day1;"));
+    }
+
+    #[test]
+    fn test_register_day() {
+        let registered = register_day(MOD_RS, 2).unwrap();
+        assert_eq!(registered, "\
+pub mod advent1;
+pub mod advent2;
+pub mod advent3;
+
+pub static DAYS: &[DaySolver] = &[
+    DaySolver { day: 1, input: Some(advent1::INPUT), run: day::run::<advent1::Day1> },
+    DaySolver { day: 2, input: Some(advent2::INPUT), run: day::run::<advent2::Day2> },
+    DaySolver { day: 3, input: Some(advent3::INPUT), run: day::run::<advent3::Day3> },
+];
+");
+
+        let registered = register_day(MOD_RS, 11).unwrap();
+        assert!(registered.contains("pub mod advent3;\npub mod advent11;\n"));
+        assert!(registered.contains("advent3::Day3> },\n    DaySolver { day: 11,"));
+    }
+
+    #[test]
+    fn test_register_errors() {
+        match register_day(MOD_RS, 3) {
+            Err(ScaffoldError::AlreadyRegistered(3)) => {}
+            other => panic!("expected AlreadyRegistered, got {:?}", other),
+        }
+
+        match register_day("pub static DAYS: &[DaySolver] = &[\n];\n", 1) {
+            Err(ScaffoldError::MissingRegistry(_)) => {}
+            other => panic!("expected MissingRegistry, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_current_registry() {
+        let mod_rs = include_str!("solutions/mod.rs");
+        for day in 1..=LAST_DAY {
+            assert!(register_day(mod_rs, day).is_err(), "day {} is not registered", day);
+        }
+    }
+}
@@ -1,111 +1,247 @@
-use std::collections::HashSet;
-
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::io::BufRead;
+
+use solutions::day::Day;
+use solutions::error::{Error, Result};
+use solutions::iter_utils::looper;
 use solutions::utils;
 
-static INPUT: &str = "data/input1";
+pub static INPUT: &str = "input1";
+
+/// The largest number of passes a drift report covers.
+pub const MAX_REPORT_PASSES: usize = 100_000;
 
 // Problem 1
 // ==================================================
 
-fn get_freq_vector() -> Vec<i32> {
-    let file_contents = utils::file_to_string(INPUT);
-
-    let freq_vector: Vec<_> = file_contents.lines().map(|line| {
-        line.parse::<i32>().unwrap()
-    }).collect();
-
-    freq_vector
+fn get_freq_vector<R: BufRead>(reader: R) -> Result<Vec<i32>> {
+    utils::parse_reader_lines(reader, utils::parse_num)
 }
 
 
-fn sum_frequencies(freqs: &Vec<i32>) -> i32 {
-    freqs.iter().sum()
+/// Summed as `i64`, so that many large changes can't overflow.
+fn sum_frequencies(freqs: &[i32]) -> i64 {
+    freqs.iter().map(|&change| i64::from(change)).sum()
 }
 
 
 // Problem 2
 // ==================================================
 
-// Implement an infinite iterator adapter
-struct Looper<T> 
-where
-    T: Iterator
-{
-    active_iter: T,
-    iter_base: T,
+/// The frequency after each number of changes in the first pass,
+/// from 0 (the start) to all but the last change.
+fn prefix_sums(freqs: &[i32]) -> Vec<i64> {
+    freqs.iter()
+        .scan(0i64, |freq, &change| {
+            let before = *freq;
+            *freq += i64::from(change);
+            Some(before)
+        })
+        .collect()
 }
 
+/// The first frequency that is reached twice while the changes are applied
+/// over and over, together with the number of changes applied until then.
+/// `None` if every frequency is reached only once.
+///
+/// After `k` full passes, the change `j` leads to `prefix[j] + k * drift`,
+/// so a frequency of the first pass comes back in a later pass exactly when
+/// another prefix sum lies a multiple of the drift ahead of it. Grouping the
+/// prefix sums by their remainder modulo the drift and sorting each group
+/// finds the nearest such pair for every prefix sum in O(n log n).
+fn first_repeat(freqs: &[i32]) -> Option<(i64, i64)> {
+    let prefix = prefix_sums(freqs);
+    let drift = sum_frequencies(freqs);
+
+    // a repeat within the first pass comes before all others; without
+    // drift the first pass ends where it started, at the latest
+    let mut seen_freqs = HashSet::new();
+    let end_of_pass = if drift == 0 { Some(&0) } else { None };
+    for (changes, &freq) in prefix.iter().chain(end_of_pass).enumerate() {
+        if !seen_freqs.insert(freq) {
+            return Some((changes as i64, freq));
+        }
+    }
 
-impl<T> Iterator for Looper<T>
-where
-    T: Iterator + Clone
-{
-     type Item = <T as Iterator>::Item;
-
-     fn next(&mut self) -> Option<Self::Item> {
-         // return the next value or restart iterator
-         match self.active_iter.next() {
-             Some(item) => Some(item),
-             None => {
-                 // reset iterator and restart
-                 self.active_iter = self.iter_base.clone();
-                 self.next()
-             }
-         }
-     }
-}
+    if drift == 0 {
+        return None;
+    }
 
+    let mut groups: HashMap<i64, Vec<(i64, usize)>> = HashMap::new();
+    for (index, &freq) in prefix.iter().enumerate() {
+        groups.entry(freq.rem_euclid(drift.abs())).or_default().push((freq, index));
+    }
 
-fn looper<T: Iterator + Clone>(iter: T) -> Looper<T> {
-    Looper {
-        active_iter: iter.clone(),
-        iter_base: iter,
+    // (changes applied, frequency) of the earliest repeat
+    let mut first_repeat: Option<(i64, i64)> = None;
+    for group in groups.values_mut() {
+        // sort in the direction of the drift, so each prefix sum is
+        // followed by the one it reaches first
+        group.sort_by_key(|&(freq, _)| freq * drift.signum());
+
+        for pair in group.windows(2) {
+            let ((from, index), (to, _)) = (pair[0], pair[1]);
+            let passes = (to - from) / drift;
+            let changes = passes * freqs.len() as i64 + index as i64;
+            if first_repeat.is_none_or(|(first, _)| changes < first) {
+                first_repeat = Some((changes, to));
+            }
+        }
     }
+
+    first_repeat
 }
 
+fn get_repeated_freq(freqs: &[i32]) -> Option<i64> {
+    first_repeat(freqs).map(|(_, freq)| freq)
+}
 
-fn get_repeated_freq(freqs: &Vec<i32>) -> i32 {
-    let mut seen_freqs = HashSet::new();
-    let mut freq_state = 0;
-    seen_freqs.insert(freq_state);
-
-    // iterate until solution found
-    for value in looper(freqs.iter()) {
-        freq_state += value;
-        
-        if seen_freqs.contains(&freq_state) {
-            return freq_state;
+// Drift analysis
+// ==================================================
+
+/// The running frequency during one pass over the changes.
+#[derive(Debug, PartialEq)]
+pub struct PassReport {
+    /// 0-based number of the pass.
+    pub pass: usize,
+    /// The frequency after the last change of the pass.
+    pub end: i64,
+    /// The lowest and highest frequency reached in the pass.
+    pub min: i64,
+    pub max: i64,
+}
+
+/// A frequency that was reached twice. Positions count the changes applied
+/// in the pass, the starting frequency is at pass 0, position 0.
+#[derive(Debug, PartialEq)]
+pub struct RepeatReport {
+    pub freq: i64,
+    /// (pass, position) where the frequency was first reached.
+    pub first: (usize, usize),
+    /// (pass, position) where it was reached the second time.
+    pub repeat: (usize, usize),
+}
+
+/// How the frequency develops over a number of passes over the changes.
+/// Its `Display` prints one `key=value` record per line.
+#[derive(Debug, PartialEq)]
+pub struct DriftReport {
+    /// The net change of every pass.
+    pub drift: i64,
+    pub passes: Vec<PassReport>,
+    /// Every frequency reached twice, in the order of the second time.
+    pub repeats: Vec<RepeatReport>,
+}
+
+impl fmt::Display for DriftReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "drift={} passes={} repeats={}", self.drift, self.passes.len(), self.repeats.len())?;
+        for pass in &self.passes {
+            writeln!(f, "pass={} end={} min={} max={}", pass.pass, pass.end, pass.min, pass.max)?;
         }
+        for repeat in &self.repeats {
+            writeln!(f, "repeat freq={} first_pass={} first_position={} repeat_pass={} repeat_position={}",
+                     repeat.freq, repeat.first.0, repeat.first.1, repeat.repeat.0, repeat.repeat.1)?;
+        }
+        Ok(())
+    }
+}
 
-        seen_freqs.insert(freq_state);
+/// Apply the changes for the given number of passes and report on them.
+/// Without a number of passes, the report runs up to the end of the pass
+/// in which the first frequency repeats (or for one pass if none does).
+/// More than `MAX_REPORT_PASSES` passes are rejected.
+pub fn drift_report(freqs: &[i32], passes: Option<usize>) -> Result<DriftReport> {
+    let passes = passes.unwrap_or_else(|| match first_repeat(freqs) {
+        Some((changes, _)) => (changes as usize - 1) / freqs.len() + 1,
+        None => 1,
+    });
+    let too_many = || {
+        Error::no_solution(format!("can't report on more than {} passes", MAX_REPORT_PASSES))
+    };
+    let total_changes = freqs.len().checked_mul(passes)
+        .filter(|_| passes <= MAX_REPORT_PASSES)
+        .ok_or_else(too_many)?;
+
+    let drift = sum_frequencies(freqs);
+    let mut report = DriftReport { drift, passes: Vec::new(), repeats: Vec::new() };
+
+    // where each frequency was reached first, and whether it was repeated
+    let mut seen_freqs: HashMap<i64, ((usize, usize), bool)> = HashMap::new();
+    seen_freqs.insert(0, ((0, 0), false));
+    let mut freq = 0;
+
+    let changes = looper(freqs.iter()).passes().take(total_changes);
+    for (index, (pass, &change)) in changes.enumerate() {
+        freq += i64::from(change);
+        let position = index % freqs.len() + 1;
+
+        if position == 1 {
+            report.passes.push(PassReport { pass, end: freq, min: freq, max: freq });
+        }
+        let pass_report = report.passes.last_mut().unwrap();
+        pass_report.end = freq;
+        pass_report.min = pass_report.min.min(freq);
+        pass_report.max = pass_report.max.max(freq);
+
+        match seen_freqs.entry(freq) {
+            Entry::Occupied(mut seen) => {
+                let (first, repeated) = seen.get_mut();
+                if !*repeated {
+                    *repeated = true;
+                    report.repeats.push(RepeatReport { freq, first: *first, repeat: (pass, position) });
+                }
+            }
+            Entry::Vacant(seen) => {
+                seen.insert(((pass, position), false));
+            }
+        }
     }
-    
-    panic!("Why are we here?");
+
+    Ok(report)
 }
 
 // Interface
 // ==================================================
 
-pub fn solution1() -> i32 {
-    let freq_vector = get_freq_vector();
-    let result = sum_frequencies(&freq_vector);
-    result
-}
+pub struct Day1;
+
+impl Day for Day1 {
+    type Input = Vec<i32>;
+    type Output1 = i64;
+    type Output2 = i64;
+
+    fn parse(input: &str) -> Result<Vec<i32>> {
+        get_freq_vector(input.as_bytes())
+    }
+
+    fn parse_reader<R: BufRead>(reader: R) -> Result<Vec<i32>> {
+        get_freq_vector(reader)
+    }
 
+    fn part1(freqs: &Vec<i32>) -> Result<i64> {
+        Ok(sum_frequencies(freqs))
+    }
 
-pub fn solution2() -> i32 {
-    let freq_vector = get_freq_vector();
-    let result = get_repeated_freq(&freq_vector);
-    result
+    fn part2(freqs: &Vec<i32>) -> Result<i64> {
+        get_repeated_freq(freqs).ok_or_else(|| Error::no_solution("no frequency is ever reached twice"))
+    }
 }
 
 
-pub fn solve_day() {
-    let solution1 = solution1();
-    println!("End frequency: {}", solution1);
+#[cfg(test)]
+fn solution1(input: &str) -> Result<i64> {
+    let freq_vector = Day1::parse(input)?;
+    Day1::part1(&freq_vector)
+}
+
 
-    let solution2 = solution2();
-    println!("First repeated frequency: {}", solution2);
+#[cfg(test)]
+fn solution2(input: &str) -> Result<i64> {
+    let freq_vector = Day1::parse(input)?;
+    Day1::part2(&freq_vector)
 }
 
 
@@ -113,13 +249,114 @@ pub fn solve_day() {
 // ================================================== 
 #[cfg(test)]
 mod test {
+    use super::*;
+
+    /// The puzzle lists the changes like "+1, -2", the input has one per line.
+    fn changes(sample: &str) -> String {
+        sample.replace(", ", "\n")
+    }
+
     #[test]
     fn test_samples1() {
-
+        assert_eq!(solution1(&changes("+1, -2, +3, +1")).unwrap(), 3);
+        assert_eq!(solution1(&changes("+1, +1, +1")).unwrap(), 3);
+        assert_eq!(solution1(&changes("+1, +1, -2")).unwrap(), 0);
+        assert_eq!(solution1(&changes("-1, -2, -3")).unwrap(), -6);
     }
 
     #[test]
     fn test_samples2() {
+        assert_eq!(solution2(&changes("+1, -2, +3, +1")).unwrap(), 2);
+        assert_eq!(solution2(&changes("+1, -1")).unwrap(), 0);
+        assert_eq!(solution2(&changes("+3, +3, +4, -2, -4")).unwrap(), 10);
+        assert_eq!(solution2(&changes("-6, +3, +8, +5, -6")).unwrap(), 5);
+        assert_eq!(solution2(&changes("+7, +7, -2, -7, -4")).unwrap(), 14);
+    }
 
+    #[test]
+    fn test_no_repeat() {
+        assert_eq!(get_repeated_freq(&[1, 1]), None);
+        assert_eq!(get_repeated_freq(&[-3, 1]), None);
+        assert!(solution2(&changes("+1, +1")).is_err());
+    }
+
+    /// Apply the changes over and over for at most `steps` changes.
+    fn brute_force_repeat(freqs: &[i32], steps: usize) -> Option<i64> {
+        let mut seen_freqs = HashSet::new();
+        let mut freq = 0;
+        seen_freqs.insert(freq);
+        for &change in looper(freqs.iter()).take(steps) {
+            freq += i64::from(change);
+            if !seen_freqs.insert(freq) {
+                return Some(freq);
+            }
+        }
+        None
+    }
+
+    #[test]
+    fn test_repeat_passes() {
+        // without changes no frequency is reached a second time
+        assert_eq!(get_repeated_freq(&[]), None);
+        assert_eq!(get_repeated_freq(&[10, -9, -11, 11]), Some(1));
+
+        // all lists of up to four changes from -3 to +3
+        for len in 1..5u32 {
+            for code in 0..7usize.pow(len) {
+                let freqs = (0..len)
+                    .map(|i| (code / 7usize.pow(i) % 7) as i32 - 3)
+                    .collect::<Vec<_>>();
+                assert_eq!(get_repeated_freq(&freqs), brute_force_repeat(&freqs, 1000), "{:?}", freqs);
+            }
+        }
+    }
+
+    #[test]
+    fn test_drift_report() {
+        // 0 | +1 -2 +3 +1 -> 1 -1 2 3 | 4 2 5 6 | 7 5 8 9
+        let report = drift_report(&[1, -2, 3, 1], Some(3)).unwrap();
+        assert_eq!(report.drift, 3);
+        assert_eq!(report.passes, vec![
+            PassReport { pass: 0, end: 3, min: -1, max: 3 },
+            PassReport { pass: 1, end: 6, min: 2, max: 6 },
+            PassReport { pass: 2, end: 9, min: 5, max: 9 },
+        ]);
+        assert_eq!(report.repeats, vec![
+            RepeatReport { freq: 2, first: (0, 3), repeat: (1, 2) },
+            RepeatReport { freq: 5, first: (1, 3), repeat: (2, 2) },
+        ]);
+
+        assert_eq!(report.to_string(), "\
+drift=3 passes=3 repeats=2
+pass=0 end=3 min=-1 max=3
+pass=1 end=6 min=2 max=6
+pass=2 end=9 min=5 max=9
+repeat freq=2 first_pass=0 first_position=3 repeat_pass=1 repeat_position=2
+repeat freq=5 first_pass=1 first_position=3 repeat_pass=2 repeat_position=2
+");
+    }
+
+    #[test]
+    fn test_drift_report_until_repeat() {
+        // 0 | 3 6 10 8 4 | 7 10 ... first repeats 10 in the second pass
+        let report = drift_report(&[3, 3, 4, -2, -4], None).unwrap();
+        assert_eq!(report.passes.len(), 2);
+        assert_eq!(report.repeats[0], RepeatReport { freq: 10, first: (0, 3), repeat: (1, 2) });
+
+        assert_eq!(drift_report(&[1, 1], None).unwrap().passes.len(), 1);
+        assert_eq!(drift_report(&[], None).unwrap().passes.len(), 0);
+        assert_eq!(drift_report(&[i32::MAX, i32::MAX], None).unwrap().drift, 2 * i64::from(i32::MAX));
+
+        assert!(drift_report(&[1, -1], Some(MAX_REPORT_PASSES + 1)).is_err());
+        assert!(drift_report(&[1, -1], Some(usize::MAX)).is_err());
+    }
+
+    #[test]
+    fn test_drift_report_start() {
+        // the starting frequency counts as reached at pass 0, position 0
+        let report = drift_report(&[1, -1], Some(1)).unwrap();
+        assert_eq!(report.repeats, vec![RepeatReport { freq: 0, first: (0, 0), repeat: (0, 2) }]);
+        let empty = DriftReport { drift: 0, passes: vec![], repeats: vec![] };
+        assert_eq!(drift_report(&[], Some(5)).unwrap(), empty);
     }
 }
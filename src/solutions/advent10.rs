@@ -0,0 +1,209 @@
+use solutions::day::Day;
+use solutions::error::{Error, Result};
+use solutions::ocr;
+use solutions::utils;
+
+pub static INPUT: &str = "input10";
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Point {
+    x: i64,
+    y: i64,
+    dx: i64,
+    dy: i64,
+}
+
+/// Find the "<a, b>" part after `label` and parse the two numbers.
+fn parse_vector(line: &str, label: &str) -> Result<(i64, i64)> {
+    let start = line.find(label)
+        .ok_or_else(|| Error::parse(format!("missing '{}' in '{}'", label, line)))? + label.len();
+    let end = line[start..].find('>')
+        .ok_or_else(|| Error::parse(format!("missing '>' after '{}' in '{}'", label, line)))?;
+
+    let mut values = line[start..(start + end)].split(',');
+    match (values.next(), values.next(), values.next()) {
+        (Some(a), Some(b), None) => Ok((utils::parse_num(a)?, utils::parse_num(b)?)),
+        _ => Err(Error::parse(format!("expected two numbers after '{}' in '{}'", label, line))),
+    }
+}
+
+impl Point {
+    /// Parse a point like "position=< 9,  1> velocity=< 0,  2>"
+    fn from_str(line: &str) -> Result<Point> {
+        let (x, y) = parse_vector(line, "position=<")?;
+        let (dx, dy) = parse_vector(line, "velocity=<")?;
+
+        Ok(Point { x, y, dx, dy })
+    }
+
+    fn at(&self, time: i64) -> (i64, i64) {
+        (self.x + time * self.dx, self.y + time * self.dy)
+    }
+}
+
+/// Bounding box (min_x, min_y, max_x, max_y) of the points at some time.
+fn bounding_box(points: &[Point], time: i64) -> (i64, i64, i64, i64) {
+    points.iter()
+        .map(|point| point.at(time))
+        .fold((i64::MAX, i64::MAX, i64::MIN, i64::MIN), |(min_x, min_y, max_x, max_y), (x, y)| {
+            (min_x.min(x), min_y.min(y), max_x.max(x), max_y.max(y))
+        })
+}
+
+fn bounding_area(points: &[Point], time: i64) -> i64 {
+    let (min_x, min_y, max_x, max_y) = bounding_box(points, time);
+    (max_x - min_x + 1) * (max_y - min_y + 1)
+}
+
+// Problem 1 and 2
+// ==================================================
+
+/// The points converge until they spell the message and then drift apart
+/// again, so the message appears when the bounding box is smallest.
+/// We simply let the points move as long as the box keeps shrinking.
+fn find_message_time(points: &[Point]) -> i64 {
+    let mut time = 0;
+    let mut area = bounding_area(points, time);
+
+    loop {
+        let next_area = bounding_area(points, time + 1);
+        if next_area >= area {
+            return time;
+        }
+
+        area = next_area;
+        time += 1;
+    }
+}
+
+/// The pixels (`grid[y][x]`) inside the bounding box that have a point at some time.
+fn pixel_grid(points: &[Point], time: i64) -> Vec<Vec<bool>> {
+    let (min_x, min_y, max_x, max_y) = bounding_box(points, time);
+    let width = (max_x - min_x + 1) as usize;
+    let height = (max_y - min_y + 1) as usize;
+
+    let mut grid = vec![vec![false; width]; height];
+    for (x, y) in points.iter().map(|point| point.at(time)) {
+        grid[(y - min_y) as usize][(x - min_x) as usize] = true;
+    }
+
+    grid
+}
+
+/// Render the points at some time as rows of '#' (point) and '.' (empty).
+pub fn render(points: &[Point], time: i64) -> String {
+    pixel_grid(points, time).iter()
+        .map(|row| row.iter().map(|&lit| if lit { '#' } else { '.' }).collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// Interface
+// ==================================================
+
+pub struct Day10;
+
+impl Day for Day10 {
+    type Input = Vec<Point>;
+    type Output1 = String;
+    type Output2 = i64;
+
+    fn parse(input: &str) -> Result<Vec<Point>> {
+        let points = utils::parse_lines(input, Point::from_str)?;
+        if points.is_empty() {
+            return Err(Error::parse("the input has no points"));
+        }
+
+        Ok(points)
+    }
+
+    fn part1(points: &Vec<Point>) -> Result<String> {
+        ocr::recognize(&pixel_grid(points, find_message_time(points)))
+    }
+
+    fn part2(points: &Vec<Point>) -> Result<i64> {
+        Ok(find_message_time(points))
+    }
+}
+
+
+// Test the sample puzzle inputs
+// ==================================================
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const TEST_POINTS: &str =
+        "position=< 9,  1> velocity=< 0,  2>
+         position=< 7,  0> velocity=<-1,  0>
+         position=< 3, -2> velocity=<-1,  1>
+         position=< 6, 10> velocity=<-2, -1>
+         position=< 2, -4> velocity=< 2,  2>
+         position=<-6, 10> velocity=< 2, -2>
+         position=< 1,  8> velocity=< 1, -1>
+         position=< 1,  7> velocity=< 1,  0>
+         position=<-3, 11> velocity=< 1, -2>
+         position=< 7,  6> velocity=<-1, -1>
+         position=<-2,  3> velocity=< 1,  0>
+         position=<-4,  3> velocity=< 2,  0>
+         position=<10, -3> velocity=<-1,  1>
+         position=< 5, 11> velocity=< 1, -2>
+         position=< 4,  7> velocity=< 0, -1>
+         position=< 8, -2> velocity=< 0,  1>
+         position=<15,  0> velocity=<-2,  0>
+         position=< 1,  6> velocity=< 1,  0>
+         position=< 8,  9> velocity=< 0, -1>
+         position=< 3,  3> velocity=<-1,  1>
+         position=< 0,  5> velocity=< 0, -1>
+         position=<-2,  2> velocity=< 2,  0>
+         position=< 5, -2> velocity=< 1,  2>
+         position=< 1,  4> velocity=< 2,  1>
+         position=<-2,  7> velocity=< 2, -2>
+         position=< 3,  6> velocity=<-1, -1>
+         position=< 5,  0> velocity=< 1,  0>
+         position=<-6,  0> velocity=< 2,  0>
+         position=< 5,  9> velocity=< 1, -2>
+         position=<14,  7> velocity=<-2,  0>
+         position=<-3,  6> velocity=< 2, -1>";
+
+    #[test]
+    fn test_samples1() {
+        let points = Day10::parse(TEST_POINTS).unwrap();
+        // the sample draws "HI" in 8 pixel high letters, which ocr doesn't read;
+        // test_render_sample checks the picture itself
+        let err = Day10::part1(&points).unwrap_err();
+        assert_eq!(err.to_string(), "no solution: no font for letters that are 8 pixels high");
+    }
+
+    #[test]
+    fn test_render_sample() {
+        let points = Day10::parse(TEST_POINTS).unwrap();
+        let message = "\
+            #...#..###\n\
+            #...#...#.\n\
+            #...#...#.\n\
+            #####...#.\n\
+            #...#...#.\n\
+            #...#...#.\n\
+            #...#...#.\n\
+            #...#..###";
+
+        assert_eq!(render(&points, 3), message);
+    }
+
+    #[test]
+    fn test_samples2() {
+        let points = Day10::parse(TEST_POINTS).unwrap();
+        assert_eq!(Day10::part2(&points).unwrap(), 3);
+    }
+
+    #[test]
+    fn test_point_from_str() {
+        assert_eq!(Point::from_str("position=< 11118, -32416> velocity=<-1,  3>").unwrap(),
+                   Point { x: 11118, y: -32416, dx: -1, dy: 3 });
+
+        let err = Day10::parse("position=< 1, 2> velocity=<1>").err().unwrap();
+        assert_eq!(err.to_string(),
+                   "line 1: expected two numbers after 'velocity=<' in 'position=< 1, 2> velocity=<1>'");
+    }
+}
@@ -0,0 +1,140 @@
+use solutions::day::Day;
+use solutions::error::Result;
+use solutions::utils;
+
+pub static INPUT: &str = "input11";
+
+const GRID_SIZE: usize = 300;
+
+fn power_level(x: usize, y: usize, serial: i64) -> i64 {
+    let rack_id = x as i64 + 10;
+    let power = (rack_id * y as i64 + serial) * rack_id;
+
+    (power / 100) % 10 - 5
+}
+
+/// Summed-area table of the power levels: `sums[y][x]` is the total power
+/// of all cells left of and above (x, y), so every square sum is O(1).
+fn summed_area_table(serial: i64) -> Vec<Vec<i64>> {
+    let mut sums = vec![vec![0; GRID_SIZE + 1]; GRID_SIZE + 1];
+    for y in 1..=GRID_SIZE {
+        for x in 1..=GRID_SIZE {
+            sums[y][x] = power_level(x, y, serial)
+                + sums[y - 1][x] + sums[y][x - 1] - sums[y - 1][x - 1];
+        }
+    }
+
+    sums
+}
+
+/// Total power of the square with top-left corner (x, y).
+fn square_power(sums: &[Vec<i64>], x: usize, y: usize, size: usize) -> i64 {
+    let (x0, y0) = (x - 1, y - 1);
+    let (x1, y1) = (x0 + size, y0 + size);
+
+    sums[y1][x1] - sums[y0][x1] - sums[y1][x0] + sums[y0][x0]
+}
+
+/// The square of a given size with the largest total power as (power, x, y).
+fn best_square(sums: &[Vec<i64>], size: usize) -> (i64, usize, usize) {
+    let mut best = (i64::MIN, 0, 0);
+    for y in 1..=(GRID_SIZE - size + 1) {
+        for x in 1..=(GRID_SIZE - size + 1) {
+            let power = square_power(sums, x, y, size);
+            if power > best.0 {
+                best = (power, x, y);
+            }
+        }
+    }
+
+    best
+}
+
+// Problem 1
+// ==================================================
+
+fn best_3x3_square(serial: i64) -> String {
+    let (_, x, y) = best_square(&summed_area_table(serial), 3);
+    format!("{},{}", x, y)
+}
+
+// Problem 2
+// ==================================================
+
+fn best_square_any_size(serial: i64) -> String {
+    let sums = summed_area_table(serial);
+    let (_, x, y, size) = (1..=GRID_SIZE)
+        .map(|size| {
+            let (power, x, y) = best_square(&sums, size);
+            (power, x, y, size)
+        })
+        // the first size with the maximum power wins
+        .fold((i64::MIN, 0, 0, 0), |best, candidate| if candidate.0 > best.0 { candidate } else { best });
+
+    format!("{},{},{}", x, y, size)
+}
+
+// Interface
+// ==================================================
+
+pub struct Day11;
+
+impl Day for Day11 {
+    type Input = i64;
+    type Output1 = String;
+    type Output2 = String;
+
+    fn parse(input: &str) -> Result<i64> {
+        utils::parse_num(input).map_err(|err| err.at_line(1))
+    }
+
+    fn part1(&serial: &i64) -> Result<String> {
+        Ok(best_3x3_square(serial))
+    }
+
+    fn part2(&serial: &i64) -> Result<String> {
+        Ok(best_square_any_size(serial))
+    }
+}
+
+
+#[cfg(test)]
+fn solution1(input: &str) -> Result<String> {
+    let serial = Day11::parse(input)?;
+    Day11::part1(&serial)
+}
+
+
+#[cfg(test)]
+fn solution2(input: &str) -> Result<String> {
+    let serial = Day11::parse(input)?;
+    Day11::part2(&serial)
+}
+
+
+// Test the sample puzzle inputs
+// ==================================================
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_power_level() {
+        assert_eq!(power_level(3, 5, 8), 4);
+        assert_eq!(power_level(122, 79, 57), -5);
+        assert_eq!(power_level(217, 196, 39), 0);
+        assert_eq!(power_level(101, 153, 71), 4);
+    }
+
+    #[test]
+    fn test_samples1() {
+        assert_eq!(solution1("18").unwrap(), "33,45");
+        assert_eq!(solution1("42").unwrap(), "21,61");
+    }
+
+    #[test]
+    fn test_samples2() {
+        assert_eq!(solution2("18").unwrap(), "90,269,16");
+        assert_eq!(solution2("42\n").unwrap(), "232,251,12");
+    }
+}
@@ -0,0 +1,225 @@
+use std::collections::HashMap;
+
+use solutions::day::Day;
+use solutions::error::{Error, Result};
+
+pub static INPUT: &str = "input12";
+
+/// Generations after which the plants are counted in the second problem.
+const LONG_RUN: u64 = 50_000_000_000;
+
+/// Generations simulated while looking for a repeating pattern; longer runs
+/// without one are given up on.
+const MAX_CYCLE_SEARCH: u64 = 1_000;
+
+/// A row of pots; `offset` is the pot number of the first entry in `pots`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Plants {
+    pots: Vec<bool>,
+    offset: i64,
+}
+
+/// The pots that contain a plant in the next generation, indexed by the
+/// five-pot neighbourhood encoded as bits (leftmost pot highest).
+type Rules = [bool; 32];
+
+fn parse_pots(pots: &str) -> Result<Vec<bool>> {
+    pots.chars()
+        .map(|pot| match pot {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err(Error::parse(format!("invalid pot '{}'", pot))),
+        })
+        .collect()
+}
+
+fn parse_rule(line: &str) -> Result<(usize, bool)> {
+    let mut parts = line.split(" => ");
+    let (pattern, result) = match (parts.next(), parts.next(), parts.next()) {
+        (Some(pattern), Some(result), None) => (parse_pots(pattern)?, parse_pots(result)?),
+        _ => return Err(Error::parse(format!("invalid rule '{}'", line))),
+    };
+
+    if pattern.len() != 5 || result.len() != 1 {
+        return Err(Error::parse(format!("invalid rule '{}'", line)));
+    }
+
+    let index = pattern.iter().fold(0, |index, &pot| (index << 1) | pot as usize);
+    Ok((index, result[0]))
+}
+
+fn trimmed(pots: Vec<bool>, offset: i64) -> Plants {
+    let first = match pots.iter().position(|&pot| pot) {
+        Some(first) => first,
+        None => return Plants { pots: Vec::new(), offset: 0 },
+    };
+    let last = pots.iter().rposition(|&pot| pot).unwrap();
+
+    Plants { pots: pots[first..=last].to_vec(), offset: offset + first as i64 }
+}
+
+/// Compute the next generation, trimming empty pots at both ends.
+fn next_generation(plants: &Plants, rules: &Rules) -> Plants {
+    let mut pots = Vec::with_capacity(plants.pots.len() + 4);
+    let mut window = 0;
+
+    // the row can grow by two pots in each direction
+    for i in 0..plants.pots.len() + 4 {
+        let pot = plants.pots.get(i).cloned().unwrap_or(false);
+        window = ((window << 1) | pot as usize) & 0b11111;
+        pots.push(rules[window]);
+    }
+
+    trimmed(pots, plants.offset - 2)
+}
+
+fn pot_sum(plants: &Plants) -> i64 {
+    plants.pots.iter()
+        .enumerate()
+        .filter(|&(_, &pot)| pot)
+        .map(|(i, _)| plants.offset + i as i64)
+        .sum()
+}
+
+// Problem 1
+// ==================================================
+
+fn sum_after(plants: &Plants, rules: &Rules, generations: u64) -> Result<i64> {
+    let mut plants = plants.clone();
+    // once a pattern repeats (possibly shifted), every later generation
+    // just moves it further, so the sum can be extrapolated
+    let mut seen: HashMap<Vec<bool>, (u64, i64)> = HashMap::new();
+
+    for generation in 0..generations {
+        if generation == MAX_CYCLE_SEARCH {
+            return Err(Error::no_solution(
+                format!("the pots don't repeat within {} generations", MAX_CYCLE_SEARCH)));
+        }
+
+        if let Some(&(previous, offset)) = seen.get(&plants.pots) {
+            let period = generation - previous;
+            let cycles = (generations - generation) / period;
+            let remaining = (generations - generation) % period;
+
+            plants.offset += cycles as i64 * (plants.offset - offset);
+            for _ in 0..remaining {
+                plants = next_generation(&plants, rules);
+            }
+            return Ok(pot_sum(&plants));
+        }
+
+        seen.insert(plants.pots.clone(), (generation, plants.offset));
+        plants = next_generation(&plants, rules);
+    }
+
+    Ok(pot_sum(&plants))
+}
+
+// Interface
+// ==================================================
+
+pub struct Day12;
+
+impl Day for Day12 {
+    type Input = (Plants, Rules);
+    type Output1 = i64;
+    type Output2 = i64;
+
+    fn parse(input: &str) -> Result<(Plants, Rules)> {
+        let mut lines = input.lines();
+        let initial = lines.next()
+            .and_then(|line| line.strip_prefix("initial state: "))
+            .ok_or_else(|| Error::parse("missing initial state").at_line(1))?;
+        let pots = parse_pots(initial.trim()).map_err(|err| err.at_line(1))?;
+
+        let mut rules = [false; 32];
+        for (i, line) in lines.enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let (index, result) = parse_rule(line.trim()).map_err(|err| err.at_line(i + 2))?;
+            rules[index] = result;
+        }
+
+        if rules[0] {
+            return Err(Error::parse("rule '..... => #' grows infinitely many plants"));
+        }
+
+        Ok((trimmed(pots, 0), rules))
+    }
+
+    fn part1(input: &(Plants, Rules)) -> Result<i64> {
+        sum_after(&input.0, &input.1, 20)
+    }
+
+    fn part2(input: &(Plants, Rules)) -> Result<i64> {
+        sum_after(&input.0, &input.1, LONG_RUN)
+    }
+}
+
+
+#[cfg(test)]
+fn solution1(input: &str) -> Result<i64> {
+    let plants = Day12::parse(input)?;
+    Day12::part1(&plants)
+}
+
+
+// Test the sample puzzle inputs
+// ==================================================
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static SAMPLE: &str = "initial state: #..#.#..##......###...###
+
+...## => #
+..#.. => #
+.#... => #
+.#.#. => #
+.#.## => #
+.##.. => #
+.#### => #
+#.#.# => #
+#.### => #
+##.#. => #
+##.## => #
+###.. => #
+###.# => #
+####. => #
+";
+
+    #[test]
+    fn test_samples1() {
+        assert_eq!(solution1(SAMPLE).unwrap(), 325);
+    }
+
+    #[test]
+    fn test_extrapolation() {
+        let (plants, rules) = Day12::parse(SAMPLE).unwrap();
+
+        let mut brute = plants.clone();
+        for _ in 0..500 {
+            brute = next_generation(&brute, &rules);
+        }
+        assert_eq!(sum_after(&plants, &rules, 500).unwrap(), pot_sum(&brute));
+    }
+
+    #[test]
+    fn test_no_cycle() {
+        // every pot next to a plant grows one, so the row only gets longer
+        let (plants, _) = Day12::parse("initial state: #").unwrap();
+        let mut rules = [true; 32];
+        rules[0] = false;
+
+        assert!(sum_after(&plants, &rules, MAX_CYCLE_SEARCH).is_ok());
+        assert!(sum_after(&plants, &rules, LONG_RUN).is_err());
+    }
+
+    #[test]
+    fn test_bad_rule() {
+        let err = Day12::parse("initial state: #.#\n\n..#. => #\n").unwrap_err();
+        assert_eq!(err.to_string(), "line 3: invalid rule '..#. => #'");
+    }
+}
@@ -0,0 +1,267 @@
+use solutions::day::Day;
+use solutions::error::{Error, Result};
+
+pub static INPUT: &str = "input13";
+
+/// Carts on separate loops never meet, so give up after this many ticks.
+const MAX_TICKS: usize = 1_000_000;
+
+/// A position on the tracks as (x, y).
+type Pos = (usize, usize);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Turn {
+    Left,
+    Straight,
+    Right,
+}
+
+#[derive(Clone, Debug)]
+pub struct Cart {
+    x: usize,
+    y: usize,
+    dx: i64,
+    dy: i64,
+    next_turn: Turn,
+    crashed: bool,
+}
+
+impl Cart {
+    fn new(x: usize, y: usize, dx: i64, dy: i64) -> Cart {
+        Cart { x, y, dx, dy, next_turn: Turn::Left, crashed: false }
+    }
+
+    /// Follow the track piece the cart is standing on.
+    fn steer(&mut self, track: u8) {
+        match track {
+            b'/' => {
+                let (dx, dy) = (-self.dy, -self.dx);
+                self.dx = dx;
+                self.dy = dy;
+            }
+            b'\\' => {
+                let (dx, dy) = (self.dy, self.dx);
+                self.dx = dx;
+                self.dy = dy;
+            }
+            b'+' => {
+                let (dx, dy) = match self.next_turn {
+                    Turn::Left => (self.dy, -self.dx),
+                    Turn::Straight => (self.dx, self.dy),
+                    Turn::Right => (-self.dy, self.dx),
+                };
+                self.dx = dx;
+                self.dy = dy;
+                self.next_turn = match self.next_turn {
+                    Turn::Left => Turn::Straight,
+                    Turn::Straight => Turn::Right,
+                    Turn::Right => Turn::Left,
+                };
+            }
+            _ => {}
+        }
+    }
+}
+
+pub struct Tracks {
+    grid: Vec<Vec<u8>>,
+    carts: Vec<Cart>,
+}
+
+/// Move all carts until `stop` returns true. Returns the crash positions
+/// in the order they happened and the remaining carts, or an error if
+/// `stop` is still false after `MAX_TICKS` ticks.
+fn simulate<F>(tracks: &Tracks, stop: F) -> Result<(Vec<Pos>, Vec<Cart>)>
+    where F: Fn(&[Pos], &[Cart]) -> bool {
+    let mut carts = tracks.carts.clone();
+    let mut crashes = Vec::new();
+
+    for _ in 0..MAX_TICKS {
+        if stop(&crashes, &carts) {
+            return Ok((crashes, carts));
+        }
+        if carts.is_empty() {
+            return Err(Error::no_solution("no carts left on the tracks"));
+        }
+
+        carts.sort_by_key(|cart| (cart.y, cart.x));
+        for i in 0..carts.len() {
+            if carts[i].crashed {
+                continue;
+            }
+
+            let cart = &mut carts[i];
+            let x = cart.x as i64 + cart.dx;
+            let y = cart.y as i64 + cart.dy;
+            let track = tracks.grid.get(y as usize)
+                .and_then(|row| row.get(x as usize))
+                .cloned()
+                .unwrap_or(b' ');
+            if x < 0 || y < 0 || track == b' ' {
+                return Err(Error::no_solution(format!("cart ran off the tracks at {},{}", x, y)));
+            }
+
+            cart.x = x as usize;
+            cart.y = y as usize;
+            cart.steer(track);
+
+            let (x, y) = (cart.x, cart.y);
+            let colliding: Vec<usize> = (0..carts.len())
+                .filter(|&j| !carts[j].crashed && carts[j].x == x && carts[j].y == y)
+                .collect();
+            if colliding.len() > 1 {
+                crashes.push((x, y));
+                for j in colliding {
+                    carts[j].crashed = true;
+                }
+            }
+        }
+
+        carts.retain(|cart| !cart.crashed);
+    }
+
+    Err(Error::no_solution(format!("carts still running after {} ticks", MAX_TICKS)))
+}
+
+// Problem 1
+// ==================================================
+
+fn first_crash(tracks: &Tracks) -> Result<String> {
+    if tracks.carts.len() < 2 {
+        return Err(Error::no_solution("a single cart cannot crash"));
+    }
+
+    let (crashes, _) = simulate(tracks, |crashes, _| !crashes.is_empty())?;
+    let (x, y) = crashes[0];
+
+    Ok(format!("{},{}", x, y))
+}
+
+// Problem 2
+// ==================================================
+
+fn last_cart(tracks: &Tracks) -> Result<String> {
+    if tracks.carts.len().is_multiple_of(2) {
+        return Err(Error::no_solution("an even number of carts can crash completely"));
+    }
+
+    let (_, carts) = simulate(tracks, |_, carts| carts.len() <= 1)?;
+    let cart = carts.first().ok_or_else(|| Error::no_solution("all carts crashed"))?;
+
+    Ok(format!("{},{}", cart.x, cart.y))
+}
+
+// Interface
+// ==================================================
+
+pub struct Day13;
+
+impl Day for Day13 {
+    type Input = Tracks;
+    type Output1 = String;
+    type Output2 = String;
+
+    fn parse(input: &str) -> Result<Tracks> {
+        let mut grid: Vec<Vec<u8>> = input.lines().map(|line| line.as_bytes().to_vec()).collect();
+        let mut carts = Vec::new();
+
+        for (y, row) in grid.iter_mut().enumerate() {
+            for (x, cell) in row.iter_mut().enumerate() {
+                let (dx, dy, track) = match *cell {
+                    b'^' => (0, -1, b'|'),
+                    b'v' => (0, 1, b'|'),
+                    b'<' => (-1, 0, b'-'),
+                    b'>' => (1, 0, b'-'),
+                    b'|' | b'-' | b'/' | b'\\' | b'+' | b' ' => continue,
+                    other => return Err(Error::parse(
+                        format!("invalid track piece '{}' in column {}", other as char, x + 1)).at_line(y + 1)),
+                };
+
+                carts.push(Cart::new(x, y, dx, dy));
+                *cell = track;
+            }
+        }
+
+        if carts.is_empty() {
+            return Err(Error::parse("no carts on the tracks"));
+        }
+
+        Ok(Tracks { grid, carts })
+    }
+
+    fn part1(tracks: &Tracks) -> Result<String> {
+        first_crash(tracks)
+    }
+
+    fn part2(tracks: &Tracks) -> Result<String> {
+        last_cart(tracks)
+    }
+}
+
+
+#[cfg(test)]
+fn solution1(input: &str) -> Result<String> {
+    let tracks = Day13::parse(input)?;
+    Day13::part1(&tracks)
+}
+
+
+#[cfg(test)]
+fn solution2(input: &str) -> Result<String> {
+    let tracks = Day13::parse(input)?;
+    Day13::part2(&tracks)
+}
+
+
+// Test the sample puzzle inputs
+// ==================================================
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_samples1() {
+        let tracks = [
+            r"/->-\        ",
+            r"|   |  /----\",
+            r"| /-+--+-\  |",
+            r"| | |  | v  |",
+            r"\-+-/  \-+--/",
+            r"  \------/   ",
+        ].join("\n");
+
+        assert_eq!(solution1(&tracks).unwrap(), "7,3");
+    }
+
+    #[test]
+    fn test_samples2() {
+        let tracks = [
+            r"/>-<\  ",
+            r"|   |  ",
+            r"| /<+-\",
+            r"| | | v",
+            r"\>+</ |",
+            r"  |   ^",
+            r"  \<->/",
+        ].join("\n");
+
+        assert_eq!(solution2(&tracks).unwrap(), "6,4");
+    }
+
+    #[test]
+    fn test_no_crash() {
+        let single = [
+            r"/>-\",
+            r"|  |",
+            r"\--/",
+        ].join("\n");
+        assert!(solution1(&single).is_err());
+
+        // carts on separate loops never meet
+        let separate = [
+            r"/>\ /<\",
+            r"\-/ \-/",
+        ].join("\n");
+        assert!(solution1(&separate).is_err());
+    }
+}
@@ -0,0 +1,131 @@
+use solutions::day::Day;
+use solutions::error::{Error, Result};
+
+pub static INPUT: &str = "input14";
+
+/// The scoreboard of the two elves, starting with recipes 3 and 7.
+struct Scoreboard {
+    scores: Vec<u8>,
+    elves: (usize, usize),
+}
+
+impl Scoreboard {
+    fn new() -> Scoreboard {
+        Scoreboard { scores: vec![3, 7], elves: (0, 1) }
+    }
+
+    /// Combine the current recipes of both elves and move them on.
+    fn step(&mut self) {
+        let (a, b) = self.elves;
+        let sum = self.scores[a] + self.scores[b];
+        if sum >= 10 {
+            self.scores.push(sum / 10);
+        }
+        self.scores.push(sum % 10);
+
+        let len = self.scores.len();
+        self.elves = ((a + 1 + self.scores[a] as usize) % len, (b + 1 + self.scores[b] as usize) % len);
+    }
+}
+
+// Problem 1
+// ==================================================
+
+fn scores_after(recipes: usize) -> String {
+    let mut board = Scoreboard::new();
+    while board.scores.len() < recipes + 10 {
+        board.step();
+    }
+
+    board.scores[recipes..recipes + 10].iter()
+        .map(|&score| (b'0' + score) as char)
+        .collect()
+}
+
+// Problem 2
+// ==================================================
+
+fn recipes_before(pattern: &[u8]) -> usize {
+    let mut board = Scoreboard::new();
+    // every start position is checked exactly once while the board grows
+    let mut checked = 0;
+    loop {
+        while checked + pattern.len() <= board.scores.len() {
+            if &board.scores[checked..checked + pattern.len()] == pattern {
+                return checked;
+            }
+            checked += 1;
+        }
+
+        board.step();
+    }
+}
+
+// Interface
+// ==================================================
+
+pub struct Day14;
+
+impl Day for Day14 {
+    type Input = Vec<u8>;
+    type Output1 = String;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<u8>> {
+        let digits = input.trim();
+        if digits.is_empty() || !digits.bytes().all(|digit| digit.is_ascii_digit()) {
+            return Err(Error::parse(format!("invalid recipe count '{}'", digits)).at_line(1));
+        }
+
+        Ok(digits.bytes().map(|digit| digit - b'0').collect())
+    }
+
+    fn part1(digits: &Vec<u8>) -> Result<String> {
+        let recipes = digits.iter()
+            .try_fold(0usize, |count, &digit| count.checked_mul(10)?.checked_add(digit as usize))
+            .ok_or_else(|| Error::no_solution("recipe count is too large"))?;
+        Ok(scores_after(recipes))
+    }
+
+    fn part2(digits: &Vec<u8>) -> Result<usize> {
+        Ok(recipes_before(digits))
+    }
+}
+
+
+#[cfg(test)]
+fn solution1(input: &str) -> Result<String> {
+    let digits = Day14::parse(input)?;
+    Day14::part1(&digits)
+}
+
+
+#[cfg(test)]
+fn solution2(input: &str) -> Result<usize> {
+    let digits = Day14::parse(input)?;
+    Day14::part2(&digits)
+}
+
+
+// Test the sample puzzle inputs
+// ==================================================
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_samples1() {
+        assert_eq!(solution1("9").unwrap(), "5158916779");
+        assert_eq!(solution1("5").unwrap(), "0124515891");
+        assert_eq!(solution1("18").unwrap(), "9251071085");
+        assert_eq!(solution1("2018").unwrap(), "5941429882");
+    }
+
+    #[test]
+    fn test_samples2() {
+        assert_eq!(solution2("51589").unwrap(), 9);
+        assert_eq!(solution2("01245").unwrap(), 5);
+        assert_eq!(solution2("92510").unwrap(), 18);
+        assert_eq!(solution2("59414").unwrap(), 2018);
+    }
+}
@@ -0,0 +1,332 @@
+use std::collections::VecDeque;
+
+use solutions::day::Day;
+use solutions::error::{Error, Result};
+
+pub static INPUT: &str = "input15";
+
+const HIT_POINTS: u32 = 200;
+const ATTACK_POWER: u32 = 3;
+
+/// A position as (row, column), so that ordering is reading order.
+type Pos = (usize, usize);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Kind {
+    Elf,
+    Goblin,
+}
+
+#[derive(Clone, Debug)]
+struct Unit {
+    kind: Kind,
+    pos: Pos,
+    hp: u32,
+    attack: u32,
+}
+
+pub struct Cave {
+    walls: Vec<Vec<bool>>,
+    units: Vec<Unit>,
+}
+
+/// The result of a combat: full rounds, remaining hit points, the winning
+/// side and how many elves died.
+struct Outcome {
+    rounds: u32,
+    hit_points: u32,
+    winner: Kind,
+    elf_deaths: usize,
+}
+
+fn neighbours(pos: Pos) -> [Pos; 4] {
+    // in reading order; positions left of or above the border wrap around
+    // and are rejected as walls
+    [(pos.0.wrapping_sub(1), pos.1), (pos.0, pos.1.wrapping_sub(1)),
+     (pos.0, pos.1 + 1), (pos.0 + 1, pos.1)]
+}
+
+/// How a round of the battle went.
+#[derive(Debug, PartialEq)]
+enum Round {
+    /// Every unit took its turn and some unit moved or attacked.
+    Played,
+    /// Every unit took its turn but none could move or attack, so no later
+    /// round will change anything either.
+    Stalled,
+    /// A unit found no enemies left, so the round ended early.
+    Ended,
+}
+
+struct Battle<'a> {
+    walls: &'a [Vec<bool>],
+    units: Vec<Unit>,
+}
+
+impl<'a> Battle<'a> {
+    fn is_open(&self, pos: Pos) -> bool {
+        let wall = self.walls.get(pos.0).and_then(|row| row.get(pos.1)).cloned().unwrap_or(true);
+        !wall && !self.units.iter().any(|unit| unit.hp > 0 && unit.pos == pos)
+    }
+
+    /// Shortest path lengths from `from` over open squares.
+    fn distances(&self, from: Pos) -> Vec<Vec<Option<usize>>> {
+        let mut dist = vec![vec![None; self.walls[0].len()]; self.walls.len()];
+        let mut queue = VecDeque::new();
+        dist[from.0][from.1] = Some(0);
+        queue.push_back(from);
+
+        while let Some(pos) = queue.pop_front() {
+            let d = dist[pos.0][pos.1].unwrap();
+            for &next in neighbours(pos).iter() {
+                if self.is_open(next) && dist[next.0][next.1].is_none() {
+                    dist[next.0][next.1] = Some(d + 1);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        dist
+    }
+
+    fn enemy_in_range(&self, unit: usize) -> Option<usize> {
+        let Unit { kind, pos, .. } = self.units[unit];
+        (0..self.units.len())
+            .filter(|&other| {
+                let other = &self.units[other];
+                other.hp > 0 && other.kind != kind && neighbours(pos).contains(&other.pos)
+            })
+            .min_by_key(|&other| (self.units[other].hp, self.units[other].pos))
+    }
+
+    /// The square a unit moves to, if it can reach any enemy.
+    fn next_step(&self, unit: usize) -> Option<Pos> {
+        let Unit { kind, pos, .. } = self.units[unit];
+        let dist = self.distances(pos);
+
+        let target = self.units.iter()
+            .filter(|other| other.hp > 0 && other.kind != kind)
+            .flat_map(|other| neighbours(other.pos).to_vec())
+            .filter(|&square| self.is_open(square))
+            .filter_map(|square| dist[square.0][square.1].map(|d| (d, square)))
+            .min()?
+            .1;
+
+        let back = self.distances(target);
+        neighbours(pos).iter()
+            .filter(|&&square| self.is_open(square))
+            .filter_map(|&square| back[square.0][square.1].map(|d| (d, square)))
+            .min()
+            .map(|(_, square)| square)
+    }
+
+    fn round(&mut self) -> Round {
+        self.units.sort_by_key(|unit| unit.pos);
+        let mut acted = false;
+
+        for unit in 0..self.units.len() {
+            if self.units[unit].hp == 0 {
+                continue;
+            }
+
+            let kind = self.units[unit].kind;
+            if !self.units.iter().any(|other| other.hp > 0 && other.kind != kind) {
+                return Round::Ended;
+            }
+
+            if self.enemy_in_range(unit).is_none() {
+                if let Some(step) = self.next_step(unit) {
+                    self.units[unit].pos = step;
+                    acted = true;
+                }
+            }
+
+            if let Some(enemy) = self.enemy_in_range(unit) {
+                let attack = self.units[unit].attack;
+                let enemy = &mut self.units[enemy];
+                enemy.hp = enemy.hp.saturating_sub(attack);
+                acted = true;
+            }
+        }
+
+        if acted { Round::Played } else { Round::Stalled }
+    }
+}
+
+/// Let the battle play out; the elves hit with the given power. Stops early
+/// if an elf dies and `stop_on_elf_death` is set. Fails if the armies can
+/// no longer reach each other.
+fn fight(cave: &Cave, elf_attack: u32, stop_on_elf_death: bool) -> Result<Outcome> {
+    let units = cave.units.iter()
+        .map(|unit| match unit.kind {
+            Kind::Elf => Unit { attack: elf_attack, ..unit.clone() },
+            Kind::Goblin => unit.clone(),
+        })
+        .collect();
+    let mut battle = Battle { walls: &cave.walls, units };
+    let elves = count_alive(&battle.units, Kind::Elf);
+
+    let mut rounds = 0;
+    loop {
+        match battle.round() {
+            Round::Played => {}
+            Round::Stalled => return Err(Error::no_solution("the elves and goblins cannot reach each other")),
+            Round::Ended => break,
+        }
+
+        rounds += 1;
+        if stop_on_elf_death && count_alive(&battle.units, Kind::Elf) < elves {
+            break;
+        }
+    }
+
+    let winner = if count_alive(&battle.units, Kind::Elf) > 0 { Kind::Elf } else { Kind::Goblin };
+    Ok(Outcome {
+        rounds,
+        hit_points: battle.units.iter().map(|unit| unit.hp).sum(),
+        winner,
+        elf_deaths: elves - count_alive(&battle.units, Kind::Elf),
+    })
+}
+
+fn count_alive(units: &[Unit], kind: Kind) -> usize {
+    units.iter().filter(|unit| unit.hp > 0 && unit.kind == kind).count()
+}
+
+// Problem 1
+// ==================================================
+
+fn combat_outcome(cave: &Cave) -> Result<u32> {
+    let outcome = fight(cave, ATTACK_POWER, false)?;
+    Ok(outcome.rounds * outcome.hit_points)
+}
+
+// Problem 2
+// ==================================================
+
+fn flawless_elf_outcome(cave: &Cave) -> Result<u32> {
+    // with enough power the elves kill every goblin with a single hit, as
+    // long as the armies can reach each other at all
+    for attack in ATTACK_POWER + 1.. {
+        let outcome = fight(cave, attack, true)?;
+        if outcome.winner == Kind::Elf && outcome.elf_deaths == 0 {
+            return Ok(outcome.rounds * outcome.hit_points);
+        }
+    }
+    unreachable!()
+}
+
+// Interface
+// ==================================================
+
+pub struct Day15;
+
+impl Day for Day15 {
+    type Input = Cave;
+    type Output1 = u32;
+    type Output2 = u32;
+
+    fn parse(input: &str) -> Result<Cave> {
+        let mut walls = Vec::new();
+        let mut units = Vec::new();
+
+        for (index, line) in input.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let y = walls.len();
+            let mut row = Vec::new();
+            for (x, square) in line.trim().chars().enumerate() {
+                let kind = match square {
+                    '#' => { row.push(true); continue; }
+                    '.' => { row.push(false); continue; }
+                    'E' => Kind::Elf,
+                    'G' => Kind::Goblin,
+                    _ => return Err(Error::parse(format!("invalid square '{}'", square)).at_line(index + 1)),
+                };
+
+                row.push(false);
+                units.push(Unit { kind, pos: (y, x), hp: HIT_POINTS, attack: ATTACK_POWER });
+            }
+            if walls.first().is_some_and(|first: &Vec<bool>| first.len() != row.len()) {
+                return Err(Error::parse("the cave map is not rectangular").at_line(index + 1));
+            }
+            walls.push(row);
+        }
+
+        if units.is_empty() {
+            return Err(Error::parse("no units in the cave"));
+        }
+
+        Ok(Cave { walls, units })
+    }
+
+    fn part1(cave: &Cave) -> Result<u32> {
+        combat_outcome(cave)
+    }
+
+    fn part2(cave: &Cave) -> Result<u32> {
+        if count_alive(&cave.units, Kind::Elf) == 0 {
+            return Err(Error::no_solution("there are no elves to win the battle"));
+        }
+        flawless_elf_outcome(cave)
+    }
+}
+
+
+#[cfg(test)]
+fn solution1(input: &str) -> Result<u32> {
+    let cave = Day15::parse(input)?;
+    Day15::part1(&cave)
+}
+
+
+#[cfg(test)]
+fn solution2(input: &str) -> Result<u32> {
+    let cave = Day15::parse(input)?;
+    Day15::part2(&cave)
+}
+
+
+// Test the sample puzzle inputs
+// ==================================================
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static SAMPLES: [&str; 6] = [
+        "#######\n#.G...#\n#...EG#\n#.#.#G#\n#..G#E#\n#.....#\n#######",
+        "#######\n#G..#E#\n#E#E.E#\n#G.##.#\n#...#E#\n#...E.#\n#######",
+        "#######\n#E..EG#\n#.#G.E#\n#E.##E#\n#G..#.#\n#..E#.#\n#######",
+        "#######\n#E.G#.#\n#.#G..#\n#G.#.G#\n#G..#.#\n#...E.#\n#######",
+        "#######\n#.E...#\n#.#..G#\n#.###.#\n#E#G#G#\n#...#G#\n#######",
+        "#########\n#G......#\n#.E.#...#\n#..##..G#\n#...##..#\n#...#...#\n#.G...G.#\n#.....G.#\n#########",
+    ];
+
+    #[test]
+    fn test_samples1() {
+        let outcomes: Vec<u32> = SAMPLES.iter().map(|sample| solution1(sample).unwrap()).collect();
+        assert_eq!(outcomes, vec![27730, 36334, 39514, 27755, 28944, 18740]);
+    }
+
+    #[test]
+    fn test_samples2() {
+        let outcomes: Vec<u32> = [0, 2, 3, 4, 5].iter().map(|&i| solution2(SAMPLES[i]).unwrap()).collect();
+        assert_eq!(outcomes, vec![4988, 31284, 3478, 6474, 1140]);
+    }
+    #[test]
+    fn test_stalemate() {
+        let cave = Day15::parse("#######\n#E.#.G#\n#######").unwrap();
+        assert!(Day15::part1(&cave).is_err());
+        assert!(Day15::part2(&cave).is_err());
+    }
+
+    #[test]
+    fn test_parse_error_lines() {
+        let error = Day15::parse("\n#####\n\n#E.G#\n#.x.#\n").err().unwrap().to_string();
+        assert!(error.contains("line 5"), "{}", error);
+        let error = Day15::parse("#####\n\n#E.G#\n####").err().unwrap().to_string();
+        assert!(error.contains("line 4"), "{}", error);
+    }
+}
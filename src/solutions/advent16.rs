@@ -0,0 +1,218 @@
+use std::collections::HashSet;
+
+use solutions::day::Day;
+use solutions::device::{Opcode, OPCODES};
+use solutions::error::{Error, Result};
+use solutions::utils;
+
+pub static INPUT: &str = "input16";
+
+/// The four registers of the device while the opcodes are unknown.
+type Registers = [usize; 4];
+
+/// An instruction with its numeric, not yet decoded, opcode.
+type RawInstruction = [usize; 4];
+
+#[derive(Debug)]
+pub struct Sample {
+    before: Registers,
+    instruction: RawInstruction,
+    after: Registers,
+}
+
+pub struct Manual {
+    samples: Vec<Sample>,
+    program: Vec<RawInstruction>,
+}
+
+fn parse_numbers(field: &str, separator: char) -> Result<[usize; 4]> {
+    let numbers = field.split(separator)
+        .map(|number| utils::parse_num(number.trim()))
+        .collect::<Result<Vec<usize>>>()?;
+    if numbers.len() != 4 {
+        return Err(Error::parse(format!("expected four numbers, got '{}'", field)));
+    }
+
+    Ok([numbers[0], numbers[1], numbers[2], numbers[3]])
+}
+
+fn parse_registers(line: &str, prefix: &str) -> Result<Registers> {
+    let registers = line.strip_prefix(prefix)
+        .map(|rest| rest.trim())
+        .and_then(|rest| rest.strip_prefix('['))
+        .and_then(|rest| rest.strip_suffix(']'))
+        .ok_or_else(|| Error::parse(format!("expected '{} [a, b, c, d]', got '{}'", prefix, line)))?;
+
+    parse_numbers(registers, ',')
+}
+
+fn parse_instruction(line: &str) -> Result<RawInstruction> {
+    parse_numbers(line.trim(), ' ')
+}
+
+/// All opcodes whose behaviour matches the sample.
+fn matching_opcodes(sample: &Sample) -> Vec<Opcode> {
+    let [_, a, b, c] = sample.instruction;
+    OPCODES.iter()
+        .cloned()
+        .filter(|op| {
+            let mut regs = sample.before;
+            op.execute(&mut regs, a, b, c).is_some() && regs == sample.after
+        })
+        .collect()
+}
+
+// Problem 1
+// ==================================================
+
+fn count_ambiguous_samples(samples: &[Sample]) -> usize {
+    samples.iter()
+        .filter(|sample| matching_opcodes(sample).len() >= 3)
+        .count()
+}
+
+// Problem 2
+// ==================================================
+
+/// Work out the opcode for every number by elimination.
+fn decode_opcodes(samples: &[Sample]) -> Result<Vec<Opcode>> {
+    let mut candidates: Vec<HashSet<Opcode>> = vec![OPCODES.iter().cloned().collect(); OPCODES.len()];
+    for sample in samples {
+        let number = sample.instruction[0];
+        let matching: HashSet<Opcode> = matching_opcodes(sample).into_iter().collect();
+        let possible = candidates.get_mut(number)
+            .ok_or_else(|| Error::no_solution(format!("there is no opcode {}", number)))?;
+        *possible = possible.intersection(&matching).cloned().collect();
+    }
+
+    let mut decoded = vec![None; OPCODES.len()];
+    while let Some(number) = candidates.iter().position(|possible| possible.len() == 1) {
+        let op = *candidates[number].iter().next().unwrap();
+        decoded[number] = Some(op);
+        for possible in candidates.iter_mut() {
+            possible.remove(&op);
+        }
+    }
+
+    decoded.into_iter()
+        .enumerate()
+        .map(|(number, op)| op.ok_or_else(
+            || Error::no_solution(format!("opcode {} can't be determined", number))))
+        .collect()
+}
+
+fn run_program(manual: &Manual) -> Result<usize> {
+    let opcodes = decode_opcodes(&manual.samples)?;
+    let mut regs = [0; 4];
+    for &[number, a, b, c] in &manual.program {
+        let op = opcodes.get(number)
+            .ok_or_else(|| Error::no_solution(format!("there is no opcode {}", number)))?;
+        op.execute(&mut regs, a, b, c)
+            .ok_or_else(|| Error::no_solution(format!("instruction {} {} {} uses a missing register", a, b, c)))?;
+    }
+
+    Ok(regs[0])
+}
+
+// Interface
+// ==================================================
+
+pub struct Day16;
+
+impl Day for Day16 {
+    type Input = Manual;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Manual> {
+        let lines: Vec<&str> = input.lines().collect();
+        let mut samples = Vec::new();
+        let mut program = Vec::new();
+
+        let mut i = 0;
+        while i < lines.len() {
+            if lines[i].starts_with("Before:") {
+                let line = |offset: usize| lines.get(i + offset).cloned().unwrap_or("");
+                let sample = Sample {
+                    before: parse_registers(line(0), "Before:").map_err(|err| err.at_line(i + 1))?,
+                    instruction: parse_instruction(line(1)).map_err(|err| err.at_line(i + 2))?,
+                    after: parse_registers(line(2), "After:").map_err(|err| err.at_line(i + 3))?,
+                };
+                samples.push(sample);
+                i += 3;
+            } else {
+                if !lines[i].trim().is_empty() {
+                    program.push(parse_instruction(lines[i]).map_err(|err| err.at_line(i + 1))?);
+                }
+                i += 1;
+            }
+        }
+
+        Ok(Manual { samples, program })
+    }
+
+    fn part1(manual: &Manual) -> Result<usize> {
+        Ok(count_ambiguous_samples(&manual.samples))
+    }
+
+    fn part2(manual: &Manual) -> Result<usize> {
+        run_program(manual)
+    }
+}
+
+
+#[cfg(test)]
+fn solution1(input: &str) -> Result<usize> {
+    let manual = Day16::parse(input)?;
+    Day16::part1(&manual)
+}
+
+
+#[cfg(test)]
+fn solution2(input: &str) -> Result<usize> {
+    let manual = Day16::parse(input)?;
+    Day16::part2(&manual)
+}
+
+
+// Test the sample puzzle inputs
+// ==================================================
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_samples1() {
+        let sample = "Before: [3, 2, 1, 1]\n9 2 1 2\nAfter:  [3, 2, 2, 1]\n";
+        let manual = Day16::parse(sample).unwrap();
+        assert_eq!(matching_opcodes(&manual.samples[0]), vec![Opcode::Addi, Opcode::Mulr, Opcode::Seti]);
+        assert_eq!(solution1(sample).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_samples2() {
+        // number the opcodes in a shuffled order and describe each one by
+        // a few samples, then run a program that computes 7 * 6
+        let number = |op: Opcode| OPCODES.iter().position(|&other| other == op).unwrap() * 5 % 16;
+        let befores = [[3, 2, 1, 1], [0, 5, 7, 2], [9, 0, 4, 6], [1, 1, 8, 3]];
+        let operands = [(2, 1, 2), (0, 3, 1), (1, 2, 0), (3, 0, 3)];
+
+        let mut input = String::new();
+        for &op in OPCODES.iter() {
+            for before in befores.iter() {
+                for &(a, b, c) in operands.iter() {
+                    let mut after = *before;
+                    op.execute(&mut after, a, b, c).unwrap();
+                    input += &format!("Before: {:?}\n{} {} {} {}\nAfter:  {:?}\n\n",
+                                      before, number(op), a, b, c, after);
+                }
+            }
+        }
+        input += &format!("\n\n{} 7 0 0\n{} 0 6 0\n", number(Opcode::Seti), number(Opcode::Muli));
+
+        let manual = Day16::parse(&input).unwrap();
+        let decoded = decode_opcodes(&manual.samples).unwrap();
+        assert!(OPCODES.iter().all(|&op| decoded[number(op)] == op));
+        assert_eq!(solution2(&input).unwrap(), 42);
+    }
+}
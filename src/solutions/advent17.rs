@@ -0,0 +1,271 @@
+use solutions::day::Day;
+use solutions::error::{Error, Result};
+use solutions::utils;
+
+pub static INPUT: &str = "input17";
+
+const SPRING_X: usize = 500;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Tile {
+    Sand,
+    Clay,
+    Flowing,
+    Settled,
+}
+
+/// A vein of clay, covering `xs` × `ys`.
+#[derive(Debug, PartialEq)]
+pub struct Vein {
+    xs: (usize, usize),
+    ys: (usize, usize),
+}
+
+fn parse_range(field: &str) -> Result<(usize, usize)> {
+    match field.find("..") {
+        Some(pos) => {
+            let (from, to) = (utils::parse_num(&field[..pos])?, utils::parse_num(&field[pos + 2..])?);
+            if from > to {
+                return Err(Error::parse(format!("empty range '{}'", field)));
+            }
+            Ok((from, to))
+        }
+        None => utils::parse_num(field).map(|value| (value, value)),
+    }
+}
+
+impl Vein {
+    /// Parse a vein like "x=495, y=2..7".
+    fn from_str(line: &str) -> Result<Vein> {
+        let invalid = || Error::parse(format!("expected a vein like 'x=495, y=2..7', got '{}'", line));
+        let mut fields = line.trim().split(", ");
+        let (first, second) = match (fields.next(), fields.next(), fields.next()) {
+            (Some(first), Some(second), None) => (first, second),
+            _ => return Err(invalid()),
+        };
+
+        if let (Some(x), Some(ys)) = (first.strip_prefix("x="), second.strip_prefix("y=")) {
+            Ok(Vein { xs: parse_range(x)?, ys: parse_range(ys)? })
+        } else if let (Some(y), Some(xs)) = (first.strip_prefix("y="), second.strip_prefix("x=")) {
+            Ok(Vein { xs: parse_range(xs)?, ys: parse_range(y)? })
+        } else {
+            Err(invalid())
+        }
+    }
+}
+
+/// The ground scan around the clay veins, with one column of sand on
+/// either side for water flowing past the outermost veins.
+struct Ground {
+    tiles: Vec<Vec<Tile>>,
+    /// The leftmost x of the veins and the spring, found in column 1.
+    min_x: usize,
+    min_y: usize,
+}
+
+impl Ground {
+    fn new(veins: &[Vein]) -> Ground {
+        // x=0 is allowed, so the margin column is kept by shifting every
+        // column one to the right rather than subtracting it from min_x
+        let min_x = veins.iter().map(|vein| vein.xs.0).min().unwrap().min(SPRING_X);
+        let max_x = veins.iter().map(|vein| vein.xs.1).max().unwrap().max(SPRING_X);
+        let min_y = veins.iter().map(|vein| vein.ys.0).min().unwrap();
+        let max_y = veins.iter().map(|vein| vein.ys.1).max().unwrap();
+
+        let mut tiles = vec![vec![Tile::Sand; max_x - min_x + 3]; max_y + 1];
+        for vein in veins {
+            for row in &mut tiles[vein.ys.0..=vein.ys.1] {
+                for tile in &mut row[vein.xs.0 - min_x + 1..=vein.xs.1 - min_x + 1] {
+                    *tile = Tile::Clay;
+                }
+            }
+        }
+
+        Ground { tiles, min_x, min_y }
+    }
+
+    fn max_y(&self) -> usize {
+        self.tiles.len() - 1
+    }
+
+    /// Let water fall from (x, y) and fill every basin it lands in.
+    fn fall(&mut self, x: usize, start: usize) {
+        let mut y = start;
+        loop {
+            self.tiles[y][x] = Tile::Flowing;
+            if y == self.max_y() {
+                return;
+            }
+
+            match self.tiles[y + 1][x] {
+                Tile::Sand => y += 1,
+                Tile::Flowing => return,
+                Tile::Clay | Tile::Settled => break,
+            }
+        }
+
+        // fill the basin row by row until the water spills over an edge
+        loop {
+            let (left, left_wall) = self.spread(x, y, false);
+            let (right, right_wall) = self.spread(x, y, true);
+
+            if left_wall && right_wall {
+                for tile in &mut self.tiles[y][left..=right] {
+                    *tile = Tile::Settled;
+                }
+                if y == start {
+                    return;
+                }
+                y -= 1;
+                continue;
+            }
+
+            for tile in &mut self.tiles[y][left..=right] {
+                *tile = Tile::Flowing;
+            }
+
+            let mut refilled = false;
+            for &(edge, wall) in [(left, left_wall), (right, right_wall)].iter() {
+                if !wall && self.tiles[y + 1][edge] == Tile::Sand {
+                    self.fall(edge, y + 1);
+                    refilled |= self.tiles[y + 1][edge] == Tile::Settled;
+                }
+            }
+
+            if !refilled {
+                return;
+            }
+        }
+    }
+
+    /// Spread water sideways from x on row y. Returns the last column the
+    /// water reaches and whether it is stopped by clay there.
+    fn spread(&self, x: usize, y: usize, right: bool) -> (usize, bool) {
+        let mut x = x;
+        loop {
+            match self.tiles[y + 1][x] {
+                Tile::Sand | Tile::Flowing => return (x, false),
+                Tile::Clay | Tile::Settled => {}
+            }
+
+            let next = if right { x + 1 } else { x - 1 };
+            if self.tiles[y][next] == Tile::Clay {
+                return (x, true);
+            }
+            x = next;
+        }
+    }
+
+    fn count(&self, matches: &dyn Fn(Tile) -> bool) -> usize {
+        self.tiles[self.min_y..].iter()
+            .map(|row| row.iter().filter(|&&tile| matches(tile)).count())
+            .sum()
+    }
+}
+
+fn flooded_ground(veins: &[Vein]) -> Ground {
+    let mut ground = Ground::new(veins);
+    let spring = SPRING_X - ground.min_x + 1;
+    ground.fall(spring, 0);
+
+    ground
+}
+
+// Problem 1
+// ==================================================
+
+fn count_wet_tiles(veins: &[Vein]) -> usize {
+    flooded_ground(veins).count(&|tile| tile == Tile::Flowing || tile == Tile::Settled)
+}
+
+// Problem 2
+// ==================================================
+
+fn count_retained_water(veins: &[Vein]) -> usize {
+    flooded_ground(veins).count(&|tile| tile == Tile::Settled)
+}
+
+// Interface
+// ==================================================
+
+pub struct Day17;
+
+impl Day for Day17 {
+    type Input = Vec<Vein>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<Vein>> {
+        let veins = utils::parse_lines(input.trim_end(), Vein::from_str)?;
+        if veins.is_empty() {
+            return Err(Error::parse("no clay veins in the scan"));
+        }
+        if veins.iter().any(|vein| vein.ys.0 == 0) {
+            return Err(Error::parse("clay veins must lie below the spring"));
+        }
+
+        Ok(veins)
+    }
+
+    fn part1(veins: &Vec<Vein>) -> Result<usize> {
+        Ok(count_wet_tiles(veins))
+    }
+
+    fn part2(veins: &Vec<Vein>) -> Result<usize> {
+        Ok(count_retained_water(veins))
+    }
+}
+
+
+#[cfg(test)]
+fn solution1(input: &str) -> Result<usize> {
+    let veins = Day17::parse(input)?;
+    Day17::part1(&veins)
+}
+
+
+#[cfg(test)]
+fn solution2(input: &str) -> Result<usize> {
+    let veins = Day17::parse(input)?;
+    Day17::part2(&veins)
+}
+
+
+// Test the sample puzzle inputs
+// ==================================================
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static SAMPLE: &str = "x=495, y=2..7
+y=7, x=495..501
+x=501, y=3..7
+x=498, y=2..4
+x=506, y=1..2
+x=498, y=10..13
+x=504, y=10..13
+y=13, x=498..504
+";
+
+    #[test]
+    fn test_samples1() {
+        assert_eq!(solution1(SAMPLE).unwrap(), 57);
+    }
+
+    #[test]
+    fn test_samples2() {
+        assert_eq!(solution2(SAMPLE).unwrap(), 29);
+    }
+
+    #[test]
+    fn test_vein_at_x0() {
+        // the water falls straight down from the spring past y=1 and y=2
+        assert_eq!(solution1("x=0, y=1..2\n").unwrap(), 2);
+    }
+
+    #[test]
+    fn test_vein() {
+        assert_eq!(Vein::from_str("y=7, x=495..501").unwrap(), Vein { xs: (495, 501), ys: (7, 7) });
+        assert!(Vein::from_str("z=7, x=495..501").is_err());
+    }
+}
@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+
+use solutions::day::Day;
+use solutions::error::{Error, Result};
+
+pub static INPUT: &str = "input18";
+
+const LONG_RUN: usize = 1_000_000_000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Acre {
+    Open,
+    Trees,
+    Lumberyard,
+}
+
+type Area = Vec<Vec<Acre>>;
+
+fn count_neighbours(area: &Area, x: usize, y: usize, acre: Acre) -> usize {
+    let rows = y.saturating_sub(1)..=(y + 1).min(area.len() - 1);
+    let count: usize = area[rows].iter()
+        .map(|row| row[x.saturating_sub(1)..=(x + 1).min(row.len() - 1)].iter()
+            .filter(|&&other| other == acre)
+            .count())
+        .sum();
+
+    // the acre itself is not a neighbour
+    count - (area[y][x] == acre) as usize
+}
+
+fn next_minute(area: &Area) -> Area {
+    (0..area.len())
+        .map(|y| (0..area[y].len())
+            .map(|x| match area[y][x] {
+                Acre::Open if count_neighbours(area, x, y, Acre::Trees) >= 3 => Acre::Trees,
+                Acre::Trees if count_neighbours(area, x, y, Acre::Lumberyard) >= 3 => Acre::Lumberyard,
+                Acre::Lumberyard if count_neighbours(area, x, y, Acre::Lumberyard) == 0
+                    || count_neighbours(area, x, y, Acre::Trees) == 0 => Acre::Open,
+                acre => acre,
+            })
+            .collect())
+        .collect()
+}
+
+fn resource_value(area: &Area) -> usize {
+    let count = |acre| area.iter().flatten().filter(|&&other| other == acre).count();
+    count(Acre::Trees) * count(Acre::Lumberyard)
+}
+
+/// The resource value after some minutes. Once an area repeats, the
+/// remaining minutes are skipped over whole cycles.
+fn value_after(area: &Area, minutes: usize) -> usize {
+    let mut area = area.clone();
+    let mut seen = HashMap::new();
+
+    let mut minute = 0;
+    while minute < minutes {
+        if let Some(previous) = seen.insert(area.clone(), minute) {
+            let period = minute - previous;
+            minute += (minutes - minute) / period * period;
+            seen.clear();
+            if minute == minutes {
+                break;
+            }
+        }
+
+        area = next_minute(&area);
+        minute += 1;
+    }
+
+    resource_value(&area)
+}
+
+// Problem 1
+// ==================================================
+
+fn value_after_ten_minutes(area: &Area) -> usize {
+    value_after(area, 10)
+}
+
+// Problem 2
+// ==================================================
+
+fn value_after_long_run(area: &Area) -> usize {
+    value_after(area, LONG_RUN)
+}
+
+// Interface
+// ==================================================
+
+pub struct Day18;
+
+impl Day for Day18 {
+    type Input = Area;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Area> {
+        // rows with their line numbers, blank lines are skipped
+        let rows = input.lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(y, line)| line.trim().chars()
+                .map(|acre| match acre {
+                    '.' => Ok(Acre::Open),
+                    '|' => Ok(Acre::Trees),
+                    '#' => Ok(Acre::Lumberyard),
+                    _ => Err(Error::parse(format!("invalid acre '{}'", acre)).at_line(y + 1)),
+                })
+                .collect::<Result<Vec<Acre>>>()
+                .map(|row| (y, row)))
+            .collect::<Result<Vec<_>>>()?;
+
+        let width = rows.first().map_or(0, |(_, row)| row.len());
+        if let Some((y, _)) = rows.iter().find(|(_, row)| row.len() != width) {
+            return Err(Error::parse("the lumber collection area is not rectangular").at_line(y + 1));
+        }
+
+        if rows.is_empty() {
+            return Err(Error::parse("the lumber collection area is empty"));
+        }
+
+        Ok(rows.into_iter().map(|(_, row)| row).collect())
+    }
+
+    fn part1(area: &Area) -> Result<usize> {
+        Ok(value_after_ten_minutes(area))
+    }
+
+    fn part2(area: &Area) -> Result<usize> {
+        Ok(value_after_long_run(area))
+    }
+}
+
+
+#[cfg(test)]
+fn solution1(input: &str) -> Result<usize> {
+    let area = Day18::parse(input)?;
+    Day18::part1(&area)
+}
+
+
+// Test the sample puzzle inputs
+// ==================================================
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static SAMPLE: &str = ".#.#...|#.
+.....#|##|
+.|..|...#.
+..|#.....#
+#.#|||#|#|
+...#.||...
+.|....|...
+||...#|.#|
+|.||||..|.
+...#.|..|.
+";
+
+    #[test]
+    fn test_samples1() {
+        assert_eq!(solution1(SAMPLE).unwrap(), 1147);
+    }
+
+    #[test]
+    fn test_cycle_skipping() {
+        let area = Day18::parse(SAMPLE).unwrap();
+        let mut brute = area.clone();
+        for _ in 0..100 {
+            brute = next_minute(&brute);
+        }
+        assert_eq!(value_after(&area, 100), resource_value(&brute));
+    }
+
+    #[test]
+    fn test_ragged_area() {
+        let err = Day18::parse("####\n#\n").unwrap_err();
+        assert_eq!(err.to_string(), "line 2: the lumber collection area is not rectangular");
+    }
+}
@@ -0,0 +1,159 @@
+use solutions::day::Day;
+use solutions::device::{Cpu, Program};
+use solutions::error::{Error, Result};
+
+pub static INPUT: &str = "input19";
+
+/// Upper bound on executed instructions before giving up on a program.
+const MAX_STEPS: usize = 100_000_000;
+
+// Problem 1
+// ==================================================
+
+fn run_background_process(program: &Program, reg0: usize) -> Result<usize> {
+    let mut cpu = Cpu::new(program, reg0);
+    for _ in 0..MAX_STEPS {
+        if !cpu.step()? {
+            return Ok(cpu.regs[0]);
+        }
+    }
+
+    Err(Error::no_solution(format!("program didn't halt after {} instructions", MAX_STEPS)))
+}
+
+// Problem 2
+// ==================================================
+
+fn sum_of_divisors(number: usize) -> usize {
+    (1..)
+        .take_while(|i| i * i <= number)
+        .filter(|&i| number.is_multiple_of(i))
+        .map(|i| if i * i == number { i } else { i + number / i })
+        .sum()
+}
+
+/// The program sums the divisors of a number it sets up first, which is
+/// far too slow to run. The setup code jumps back to the start of the
+/// main loop (instruction 1); at that point the number is the largest
+/// register, and the sum is computed directly.
+fn run_long_process(program: &Program) -> Result<usize> {
+    let mut cpu = Cpu::new(program, 1);
+    let mut set_up = false;
+
+    for _ in 0..MAX_STEPS {
+        if !cpu.step()? {
+            return Ok(cpu.regs[0]);
+        }
+
+        if cpu.ip > 1 {
+            set_up = true;
+        } else if set_up && cpu.ip == 1 {
+            return Ok(sum_of_divisors(*cpu.regs.iter().max().unwrap()));
+        }
+    }
+
+    Err(Error::no_solution(format!("program didn't halt after {} instructions", MAX_STEPS)))
+}
+
+// Interface
+// ==================================================
+
+pub struct Day19;
+
+impl Day for Day19 {
+    type Input = Program;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Program> {
+        input.trim_end().parse()
+    }
+
+    fn part1(program: &Program) -> Result<usize> {
+        run_background_process(program, 0)
+    }
+
+    fn part2(program: &Program) -> Result<usize> {
+        run_long_process(program)
+    }
+}
+
+
+#[cfg(test)]
+fn solution1(input: &str) -> Result<usize> {
+    let program = Day19::parse(input)?;
+    Day19::part1(&program)
+}
+
+
+#[cfg(test)]
+fn solution2(input: &str) -> Result<usize> {
+    let program = Day19::parse(input)?;
+    Day19::part2(&program)
+}
+
+
+// Test the sample puzzle inputs
+// ==================================================
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static SAMPLE: &str = "#ip 0
+seti 5 0 1
+seti 6 0 2
+addi 0 1 0
+addr 1 2 3
+setr 1 0 0
+seti 8 0 4
+seti 9 0 5
+";
+
+    #[test]
+    fn test_samples1() {
+        assert_eq!(solution1(SAMPLE).unwrap(), 6);
+    }
+
+    /// Sums the divisors of 10, or of 100 if register 0 starts as 1.
+    static DIVISOR_SUM: &str = "#ip 5
+addi 5 16 5
+seti 1 0 1
+seti 1 0 3
+mulr 1 3 4
+eqrr 4 2 4
+addr 4 5 5
+addi 5 1 5
+addr 1 0 0
+addi 3 1 3
+gtrr 3 2 4
+addr 5 4 5
+seti 2 0 5
+addi 1 1 1
+gtrr 1 2 4
+addr 4 5 5
+seti 1 0 5
+mulr 5 5 5
+seti 10 0 2
+addr 5 0 5
+seti 0 0 5
+seti 100 0 2
+seti 0 0 0
+seti 0 0 5
+";
+
+    #[test]
+    fn test_samples2() {
+        assert_eq!(solution1(DIVISOR_SUM).unwrap(), 18);
+        assert_eq!(solution2(DIVISOR_SUM).unwrap(), 217);
+
+        let program = Day19::parse(DIVISOR_SUM).unwrap();
+        assert_eq!(run_background_process(&program, 1).unwrap(), 217);
+    }
+
+    #[test]
+    fn test_sum_of_divisors() {
+        assert_eq!(sum_of_divisors(1), 1);
+        assert_eq!(sum_of_divisors(16), 31);
+        assert_eq!(sum_of_divisors(10551264), 27941760);
+    }
+}
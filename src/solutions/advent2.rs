@@ -1,108 +1,124 @@
+use std::collections::hash_map::Entry;
 use std::collections::HashMap;
-use std::collections::HashSet;
+use std::io::BufRead;
 
+use solutions::day::Day;
+use solutions::error::{Error, Result};
 use solutions::utils;
 
-static INPUT: &str = "data/input2";
+pub static INPUT: &str = "input2";
 
 // Problem 1
 // ==================================================
 
-// we operate over chars, not unicode code points, but
-// it doesn't matter since we mostly have ascii here
-fn count_frequencies(id: &str) -> HashMap<char, u16> {
-    let mut char_count_map = HashMap::new();
-    for c in id.chars() {
-        *char_count_map.entry(c).or_insert(0) += 1;
-    }
-
-    char_count_map
+/// The puzzle's checksum counts IDs with a letter exactly twice and thrice.
+const CHECKSUM_MULTIPLICITIES: [usize; 2] = [2, 3];
+
+/// The number of IDs with some letter that appears exactly `multiplicity`
+/// times, for every requested multiplicity, and the product of the counts.
+#[derive(Debug, PartialEq)]
+pub struct Checksum {
+    /// (multiplicity, number of IDs), in the requested order.
+    pub counts: Vec<(usize, u64)>,
+    /// `None` only if the product does not fit even in a `u128`.
+    pub product: Option<u128>,
 }
 
-fn add_counts(id: &str, twos: &mut u16, threes: &mut u16) {
-    let char_counts = count_frequencies(id);
-    let char_counts: HashSet<&u16> = char_counts.values().collect();
+/// Count the letters of every ID as bytes, so the letters should be ASCII.
+pub fn checksum<S: AsRef<[u8]>>(ids: &[S], multiplicities: &[usize]) -> Checksum {
+    let mut counts: Vec<(usize, u64)> = multiplicities.iter()
+        .map(|&multiplicity| (multiplicity, 0))
+        .collect();
+    let mut letter_counts = [0usize; 256];
 
-    if char_counts.contains(&2) {
-        *twos +=1;
-    }
-
-    if char_counts.contains(&3) {
-        *threes +=1;
-    }
-}
+    for id in ids {
+        let id = id.as_ref();
+        for &letter in id {
+            letter_counts[letter as usize] += 1;
+        }
 
-fn compute_checksum(ids: Vec<&str>) -> u16 {
-    let mut twos = 0;
-    let mut threes = 0;
+        for (multiplicity, ids_with) in counts.iter_mut() {
+            if id.iter().any(|&letter| letter_counts[letter as usize] == *multiplicity) {
+                *ids_with += 1;
+            }
+        }
 
-    for id in ids {
-        add_counts(id, &mut twos, &mut threes);
-    };
+        // reset just the letters this ID counted
+        for &letter in id {
+            letter_counts[letter as usize] = 0;
+        }
+    }
 
-    twos*threes
+    let product = counts.iter()
+        .try_fold(1u128, |product, &(_, ids_with)| product.checked_mul(u128::from(ids_with)));
+    Checksum { counts, product }
 }
 
 
 // Problem 2
 // ==================================================
 
-fn match_common_string(id1: &str, id2: &str) -> Option<String> {
-    let mut common_string = String::new();
-
-    let mut mismatch_count = 0;
-    for (c1, c2) in id1.chars().zip(id2.chars()) {
-        if c1 == c2 {
-            common_string.push(c1);
-        }
-        else {
-            mismatch_count += 1;
-            if mismatch_count > 1 {
-                return None;
-            }
-        }
-    }
-
-    Some(common_string)
-}
+/// The common characters of the first two IDs that differ in exactly one
+/// character, or `None` if there are no such IDs.
+///
+/// Every ID is stored once per position with that character masked out,
+/// keyed by the text before and after it. Two IDs that differ only at that
+/// position share the key, so one pass over the IDs finds them.
+fn get_common_string<S: AsRef<str>>(ids: &[S]) -> Option<String> {
+    let mut masked_ids: HashMap<(&str, &str), char> = HashMap::new();
 
-fn get_common_string(ids: Vec<&str>) -> String {
-    for i in 0..ids.len() {
-        for j in (i+1)..ids.len() {
-            if let Some(common) = match_common_string(ids[i], ids[j]) {
-                return common
+    for id in ids {
+        let id = id.as_ref();
+        for (pos, c) in id.char_indices() {
+            let key = (&id[..pos], &id[pos + c.len_utf8()..]);
+            match masked_ids.entry(key) {
+                // identical IDs share all keys with the same character
+                Entry::Occupied(masked) if *masked.get() != c => return Some(key.0.to_owned() + key.1),
+                Entry::Occupied(_) => {}
+                Entry::Vacant(masked) => {
+                    masked.insert(c);
+                }
             }
         }
     }
 
-    String::from("Impossible!")
+    None
 }
 
 // Interface
 // ==================================================
 
-pub fn solution1() -> u16 {
-    let ids = utils::file_to_string(INPUT);
-    let ids: Vec<&str> = ids.lines().collect();
+pub struct Day2;
 
-    compute_checksum(ids)
-}
+impl Day for Day2 {
+    type Input = Vec<String>;
+    type Output1 = u128;
+    type Output2 = String;
 
+    fn parse(input: &str) -> Result<Vec<String>> {
+        Ok(input.lines().map(String::from).collect())
+    }
 
-pub fn solution2() -> String {
-    let ids = utils::file_to_string(INPUT);
-    let ids: Vec<&str> = ids.lines().collect();
+    fn parse_reader<R: BufRead>(reader: R) -> Result<Vec<String>> {
+        utils::read_lines(reader).collect()
+    }
 
-    get_common_string(ids)
-}
+    fn part1(ids: &Vec<String>) -> Result<u128> {
+        checksum(ids, &CHECKSUM_MULTIPLICITIES).product
+            .ok_or_else(|| Error::no_solution("the checksum does not fit in 128 bits"))
+    }
 
+    fn part2(ids: &Vec<String>) -> Result<String> {
+        get_common_string(ids)
+            .ok_or_else(|| Error::no_solution("no two IDs differ in exactly one character"))
+    }
+}
 
-pub fn solve_day() {
-    let solution1 = solution1();
-    println!("Checksum: {}", solution1);
 
-    let solution2 = solution2();
-    println!("Common string: {}", solution2);
+#[cfg(test)]
+fn solution2(input: &str) -> Result<String> {
+    let ids = Day2::parse(input)?;
+    Day2::part2(&ids)
 }
 
 
@@ -117,8 +133,22 @@ mod test {
         let test_ids = vec!["abcdef", "bababc", "abbcde", "abcccd", 
                             "aabcdd", "abcdee", "ababab"];
 
-        let checksum = compute_checksum(test_ids);
-        assert_eq!(checksum, 12);
+        let checksum = checksum(&test_ids, &CHECKSUM_MULTIPLICITIES);
+        assert_eq!(checksum, Checksum { counts: vec![(2, 4), (3, 3)], product: Some(12) });
+    }
+
+    #[test]
+    fn test_checksum_multiplicities() {
+        let test_ids = ["abcdef", "bababc", "abbcde", "abcccd", "aabcdd", "abcdee", "ababab"];
+
+        let counts = checksum(&test_ids, &[1, 4]);
+        assert_eq!(counts, Checksum { counts: vec![(1, 6), (4, 0)], product: Some(0) });
+        assert_eq!(checksum(&test_ids, &[]).product, Some(1));
+
+        // (2^17)^4 overflows a u64 but not a u128, (2^17)^8 overflows both
+        let many = vec!["aa"; 1 << 17];
+        assert_eq!(checksum(&many, &[2; 4]).product, Some(1 << 68));
+        assert_eq!(checksum(&many, &[2; 8]).product, None);
     }
 
     #[test]
@@ -126,8 +156,22 @@ mod test {
         let test_ids = vec!["abcde", "fghij", "klmno", "pqrst", 
                             "fguij", "axcye", "wvxyz"];
 
-        let common_string = get_common_string(test_ids);
-        assert_eq!(common_string, "fgij");
+        let common_string = get_common_string(&test_ids);
+        assert_eq!(common_string.unwrap(), "fgij");
+    }
+
+    #[test]
+    fn test_no_common_string() {
+        // identical IDs do not count, neither do IDs that differ in two places
+        assert_eq!(get_common_string(&["abcde", "abcde", "axcye"]), None);
+        assert_eq!(get_common_string(&["abcde", "abcde", "abxde"]).unwrap(), "abde");
+        assert!(solution2("abc\nxyz\n").is_err());
+    }
+
+    #[test]
+    fn test_unicode_ids() {
+        assert_eq!(get_common_string(&["añb", "aöb"]).unwrap(), "ab");
+        assert_eq!(get_common_string(&["ñab", "ñac"]).unwrap(), "ña");
     }
 
 }
@@ -0,0 +1,165 @@
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use solutions::day::Day;
+use solutions::error::{Error, Result};
+
+pub static INPUT: &str = "input20";
+
+const FAR_AWAY: usize = 1000;
+
+type Pos = (i32, i32);
+
+/// Rooms reachable through one door from each room.
+type Map = HashMap<Pos, HashSet<Pos>>;
+
+/// Follow every path described by the regex from the starting room and
+/// record the doors on the way.
+fn build_map(regex: &str) -> Result<Map> {
+    let body = regex.trim()
+        .strip_prefix('^')
+        .and_then(|body| body.strip_suffix('$'))
+        .ok_or_else(|| Error::parse("the route regex must start with '^' and end with '$'"))?;
+
+    let mut map = Map::new();
+    let mut current: HashSet<Pos> = [(0, 0)].iter().cloned().collect();
+    // for each open group: the rooms it starts from and where its
+    // finished branches end up
+    let mut groups: Vec<(HashSet<Pos>, HashSet<Pos>)> = Vec::new();
+
+    for (i, c) in body.chars().enumerate() {
+        let (dx, dy) = match c {
+            'N' => (0, -1),
+            'E' => (1, 0),
+            'S' => (0, 1),
+            'W' => (-1, 0),
+            '(' => {
+                groups.push((current.clone(), HashSet::new()));
+                continue;
+            }
+            '|' | ')' => {
+                let (start, mut ends) = groups.pop()
+                    .ok_or_else(|| Error::parse(format!("unmatched '{}' at position {}", c, i + 1)))?;
+                ends.extend(current.drain());
+                if c == '|' {
+                    current = start.clone();
+                    groups.push((start, ends));
+                } else {
+                    current = ends;
+                }
+                continue;
+            }
+            _ => return Err(Error::parse(format!("invalid direction '{}' at position {}", c, i + 1))),
+        };
+
+        current = current.iter()
+            .map(|&(x, y)| {
+                let next = (x + dx, y + dy);
+                map.entry((x, y)).or_default().insert(next);
+                map.entry(next).or_default().insert((x, y));
+                next
+            })
+            .collect();
+    }
+
+    if !groups.is_empty() {
+        return Err(Error::parse("unclosed '(' in the route regex"));
+    }
+
+    Ok(map)
+}
+
+/// The fewest doors to pass to get to each room.
+fn door_distances(map: &Map) -> HashMap<Pos, usize> {
+    let mut distances = HashMap::new();
+    let mut queue = VecDeque::new();
+    distances.insert((0, 0), 0);
+    queue.push_back((0, 0));
+
+    while let Some(room) = queue.pop_front() {
+        let distance = distances[&room];
+        for &next in map.get(&room).into_iter().flatten() {
+            if let Entry::Vacant(entry) = distances.entry(next) {
+                entry.insert(distance + 1);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    distances
+}
+
+// Problem 1
+// ==================================================
+
+fn furthest_room(map: &Map) -> usize {
+    door_distances(map).values().cloned().max().unwrap_or(0)
+}
+
+// Problem 2
+// ==================================================
+
+fn count_far_rooms(map: &Map) -> usize {
+    door_distances(map).values().filter(|&&distance| distance >= FAR_AWAY).count()
+}
+
+// Interface
+// ==================================================
+
+pub struct Day20;
+
+impl Day for Day20 {
+    type Input = Map;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Map> {
+        build_map(input).map_err(|err| err.at_line(1))
+    }
+
+    fn part1(map: &Map) -> Result<usize> {
+        Ok(furthest_room(map))
+    }
+
+    fn part2(map: &Map) -> Result<usize> {
+        Ok(count_far_rooms(map))
+    }
+}
+
+
+#[cfg(test)]
+fn solution1(input: &str) -> Result<usize> {
+    let map = Day20::parse(input)?;
+    Day20::part1(&map)
+}
+
+
+// Test the sample puzzle inputs
+// ==================================================
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_samples1() {
+        assert_eq!(solution1("^WNE$").unwrap(), 3);
+        assert_eq!(solution1("^ENWWW(NEEE|SSE(EE|N))$").unwrap(), 10);
+        assert_eq!(solution1("^ENNWSWW(NEWS|)SSSEEN(WNSE|)EE(SWEN|)NNN$").unwrap(), 18);
+        assert_eq!(solution1("^ESSWWN(E|NNENN(EESS(WNSE|)SSS|WWWSSSSE(SW|NNNE)))$").unwrap(), 23);
+        assert_eq!(solution1("^WSSEESWWWNW(S|NENNEEEENN(ESSSSW(NWSW|SSEN)|WSWWN(E|WWS(E|SS))))$").unwrap(), 31);
+    }
+
+    #[test]
+    fn test_samples2() {
+        let map = Day20::parse("^ENWWW(NEEE|SSE(EE|N))$").unwrap();
+        let distances = door_distances(&map);
+        assert_eq!(distances.values().filter(|&&distance| distance >= 5).count(), 11);
+    }
+
+    #[test]
+    fn test_bad_regex() {
+        let err = Day20::parse("^EN(W|S$").unwrap_err();
+        assert_eq!(err.to_string(), "line 1: unclosed '(' in the route regex");
+        assert!(Day20::parse("^ENX$").is_err());
+    }
+}
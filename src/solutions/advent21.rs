@@ -0,0 +1,209 @@
+use std::collections::HashSet;
+
+use solutions::day::Day;
+use solutions::device::{Cpu, Instruction, Opcode, Program};
+use solutions::error::{Error, Result};
+
+pub static INPUT: &str = "input21";
+
+/// Upper bound on executed instructions before giving up on a program.
+const MAX_STEPS: usize = 1_000_000_000;
+
+/// The only instruction that reads register 0: it halts the program when
+/// the other register equals register 0. Returns its index and the other
+/// register.
+fn find_halting_check(program: &Program) -> Result<(usize, usize)> {
+    program.instructions.iter()
+        .position(|ins| ins.op == Opcode::Eqrr && (ins.a == 0) != (ins.b == 0))
+        .map(|index| {
+            let ins = &program.instructions[index];
+            (index, if ins.a == 0 { ins.b } else { ins.a })
+        })
+        .ok_or_else(|| Error::no_solution("program never compares register 0"))
+}
+
+/// Detect a loop at `start` that divides register `n` by a constant the
+/// slow way, counting t up until (t + 1) * k > n:
+///
+/// ```text
+/// seti 0 _ t; addi t 1 u; muli u k u; gtrr u n u; addr u ip ip;
+/// addi ip 1 ip; seti start+8 _ ip; addi t 1 t; seti start _ ip; setr t _ n
+/// ```
+///
+/// Returns (t, u, k, n) if the pattern matches.
+fn division_loop(program: &Program, start: usize) -> Option<(usize, usize, usize, usize)> {
+    let code = program.instructions.get(start..start + 10)?;
+    let ip = program.ip_register;
+    let is = |ins: &Instruction, op: Opcode, a: usize, b: Option<usize>, c: usize| {
+        ins.op == op && ins.a == a && b.is_none_or(|b| ins.b == b) && ins.c == c
+    };
+
+    let t = code[0].c;
+    let u = code[1].c;
+    let k = code[2].b;
+    let n = code[3].b;
+    let matches = is(&code[0], Opcode::Seti, 0, None, t)
+        && is(&code[1], Opcode::Addi, t, Some(1), u)
+        && is(&code[2], Opcode::Muli, u, Some(k), u)
+        && is(&code[3], Opcode::Gtrr, u, Some(n), u)
+        && is(&code[4], Opcode::Addr, u, Some(ip), ip)
+        && is(&code[5], Opcode::Addi, ip, Some(1), ip)
+        && is(&code[6], Opcode::Seti, start + 8, None, ip)
+        && is(&code[7], Opcode::Addi, t, Some(1), t)
+        && is(&code[8], Opcode::Seti, start, None, ip)
+        && is(&code[9], Opcode::Setr, t, None, n);
+
+    if matches && k > 0 && ![t, u, n].contains(&ip) {
+        Some((t, u, k, n))
+    } else {
+        None
+    }
+}
+
+/// Run the program as if register 0 never matched, reporting every value
+/// it is compared to until `report` returns false.
+fn compared_values<F: FnMut(usize) -> bool>(program: &Program, mut report: F) -> Result<()> {
+    let (check, register) = find_halting_check(program)?;
+    let mut cpu = Cpu::new(program, 0);
+
+    for _ in 0..MAX_STEPS {
+        if cpu.ip == check {
+            if !report(cpu.regs[register]) {
+                return Ok(());
+            }
+
+            // skip the comparison as if it failed
+            let Instruction { c, .. } = program.instructions[check];
+            cpu.regs[program.ip_register] = cpu.ip;
+            cpu.regs[c] = 0;
+            cpu.ip = cpu.regs[program.ip_register] + 1;
+            continue;
+        }
+
+        if let Some((t, u, k, n)) = division_loop(program, cpu.ip) {
+            cpu.regs[t] = cpu.regs[n] / k;
+            cpu.regs[u] = 1;
+            cpu.ip += 9;
+            continue;
+        }
+
+        if !cpu.step()? {
+            return Err(Error::no_solution("program halted without comparing register 0"));
+        }
+    }
+
+    Err(Error::no_solution(format!("no result after {} instructions", MAX_STEPS)))
+}
+
+// Problem 1
+// ==================================================
+
+fn fewest_instructions_value(program: &Program) -> Result<usize> {
+    let mut first = None;
+    compared_values(program, |value| {
+        first = Some(value);
+        false
+    })?;
+
+    Ok(first.unwrap())
+}
+
+// Problem 2
+// ==================================================
+
+fn most_instructions_value(program: &Program) -> Result<usize> {
+    // the compared values eventually cycle; the last new one takes longest
+    let mut seen = HashSet::new();
+    let mut last = None;
+    compared_values(program, |value| {
+        if !seen.insert(value) {
+            return false;
+        }
+        last = Some(value);
+        true
+    })?;
+
+    Ok(last.unwrap())
+}
+
+// Interface
+// ==================================================
+
+pub struct Day21;
+
+impl Day for Day21 {
+    type Input = Program;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Program> {
+        input.trim_end().parse()
+    }
+
+    fn part1(program: &Program) -> Result<usize> {
+        fewest_instructions_value(program)
+    }
+
+    fn part2(program: &Program) -> Result<usize> {
+        most_instructions_value(program)
+    }
+}
+
+
+#[cfg(test)]
+fn solution1(input: &str) -> Result<usize> {
+    let program = Day21::parse(input)?;
+    Day21::part1(&program)
+}
+
+
+#[cfg(test)]
+fn solution2(input: &str) -> Result<usize> {
+    let program = Day21::parse(input)?;
+    Day21::part2(&program)
+}
+
+
+// Test the sample puzzle inputs
+// ==================================================
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_samples1() {
+        // the puzzle has no sample, this program compares 3, 6, 1, 4, 7, 2, 5, 0, 3, ...
+        let program = "#ip 4\nseti 0 0 1\naddi 1 3 1\nbani 1 7 1\neqrr 1 0 2\naddr 2 4 4\nseti 0 0 4\n";
+        assert_eq!(solution1(program).unwrap(), 3);
+        assert_eq!(solution2(program).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_division_loop() {
+        // r5 = 1000 / 256 the slow way, then compare r5 to register 0
+        let program = "#ip 2
+seti 1000 0 5
+seti 0 0 4
+addi 4 1 1
+muli 1 256 1
+gtrr 1 5 1
+addr 1 2 2
+addi 2 1 2
+seti 9 0 2
+addi 4 1 4
+seti 1 0 2
+setr 4 0 5
+eqrr 5 0 1
+";
+        let parsed = Day21::parse(program).unwrap();
+        assert_eq!(division_loop(&parsed, 1), Some((4, 1, 256, 5)));
+        assert_eq!(division_loop(&parsed, 0), None);
+        assert_eq!(solution1(program).unwrap(), 3);
+    }
+
+    #[test]
+    fn test_no_check() {
+        let err = solution1("#ip 0\nseti 5 0 1\n").unwrap_err();
+        assert_eq!(err.to_string(), "no solution: program never compares register 0");
+    }
+}
@@ -0,0 +1,194 @@
+use std::collections::HashMap;
+
+use binary_heap_plus::BinaryHeap;
+
+use solutions::day::Day;
+use solutions::error::{Error, Result};
+use solutions::utils;
+
+pub static INPUT: &str = "input22";
+
+const SWITCH_MINUTES: usize = 7;
+
+/// Region types are the erosion level modulo 3: rocky, wet and narrow.
+/// Tools are numbered so that tool `t` can't be used in region type `t`:
+/// neither in rocky, the torch in wet and climbing gear in narrow regions.
+const TORCH: usize = 1;
+
+#[derive(Debug, PartialEq)]
+pub struct Scan {
+    depth: usize,
+    target: (usize, usize),
+}
+
+/// Erosion levels of the cave, computed lazily as the search goes further.
+struct Cave<'a> {
+    scan: &'a Scan,
+    erosion: Vec<Vec<usize>>,
+}
+
+impl<'a> Cave<'a> {
+    fn new(scan: &'a Scan) -> Cave<'a> {
+        Cave { scan, erosion: Vec::new() }
+    }
+
+    fn geologic_index(&self, x: usize, y: usize) -> usize {
+        match (x, y) {
+            (0, 0) => 0,
+            _ if (x, y) == self.scan.target => 0,
+            (x, 0) => x * 16807,
+            (0, y) => y * 48271,
+            (x, y) => self.erosion[y][x - 1] * self.erosion[y - 1][x],
+        }
+    }
+
+    /// Make sure the erosion levels up to (x, y) are known.
+    fn grow(&mut self, x: usize, y: usize) {
+        let width = self.erosion.first().map_or(0, |row| row.len());
+        if x < width && y < self.erosion.len() {
+            return;
+        }
+
+        // grow generously so that the search rarely has to recompute
+        let (width, height) = ((x + 1).max(width * 2), (y + 1).max(self.erosion.len() * 2));
+        self.erosion = vec![vec![0; width]; height];
+        for y in 0..height {
+            for x in 0..width {
+                self.erosion[y][x] = (self.geologic_index(x, y) + self.scan.depth) % 20183;
+            }
+        }
+    }
+
+    fn region_type(&mut self, x: usize, y: usize) -> usize {
+        self.grow(x, y);
+        self.erosion[y][x] % 3
+    }
+}
+
+// Problem 1
+// ==================================================
+
+fn risk_level(scan: &Scan) -> usize {
+    let mut cave = Cave::new(scan);
+    let (tx, ty) = scan.target;
+
+    (0..=ty)
+        .flat_map(|y| (0..=tx).map(move |x| (x, y)))
+        .map(|(x, y)| cave.region_type(x, y))
+        .sum()
+}
+
+// Problem 2
+// ==================================================
+
+fn rescue_minutes(scan: &Scan) -> usize {
+    let mut cave = Cave::new(scan);
+    let start = (0, 0, TORCH);
+    let goal = (scan.target.0, scan.target.1, TORCH);
+
+    let mut minutes = HashMap::new();
+    let mut queue = BinaryHeap::new_min();
+    minutes.insert(start, 0);
+    queue.push((0, start));
+
+    while let Some((time, state)) = queue.pop() {
+        if state == goal {
+            return time;
+        }
+        if minutes.get(&state).is_some_and(|&best| best < time) {
+            continue;
+        }
+
+        let (x, y, tool) = state;
+        let region = cave.region_type(x, y);
+
+        let mut moves = vec![(time + SWITCH_MINUTES, (x, y, 3 - region - tool))];
+        let neighbours = [(x.wrapping_sub(1), y), (x + 1, y), (x, y.wrapping_sub(1)), (x, y + 1)];
+        for &(nx, ny) in neighbours.iter().filter(|&&(nx, ny)| nx != usize::MAX && ny != usize::MAX) {
+            if cave.region_type(nx, ny) != tool {
+                moves.push((time + 1, (nx, ny, tool)));
+            }
+        }
+
+        for (next_time, next) in moves {
+            if minutes.get(&next).is_none_or(|&best| next_time < best) {
+                minutes.insert(next, next_time);
+                queue.push((next_time, next));
+            }
+        }
+    }
+
+    unreachable!("the cave extends indefinitely, so the target is always reachable")
+}
+
+// Interface
+// ==================================================
+
+pub struct Day22;
+
+impl Day for Day22 {
+    type Input = Scan;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Scan> {
+        let mut lines = input.lines();
+        let depth = lines.next()
+            .and_then(|line| line.strip_prefix("depth: "))
+            .ok_or_else(|| Error::parse("expected 'depth: N'").at_line(1))?;
+        let target = lines.next()
+            .and_then(|line| line.strip_prefix("target: "))
+            .ok_or_else(|| Error::parse("expected 'target: X,Y'").at_line(2))?;
+
+        let depth = utils::parse_num(depth.trim()).map_err(|err| err.at_line(1))?;
+        let mut coords = target.trim().split(',');
+        let target = match (coords.next(), coords.next(), coords.next()) {
+            (Some(x), Some(y), None) => (utils::parse_num(x)?, utils::parse_num(y)?),
+            _ => return Err(Error::parse("expected 'target: X,Y'").at_line(2)),
+        };
+
+        Ok(Scan { depth, target })
+    }
+
+    fn part1(scan: &Scan) -> Result<usize> {
+        Ok(risk_level(scan))
+    }
+
+    fn part2(scan: &Scan) -> Result<usize> {
+        Ok(rescue_minutes(scan))
+    }
+}
+
+
+#[cfg(test)]
+fn solution1(input: &str) -> Result<usize> {
+    let scan = Day22::parse(input)?;
+    Day22::part1(&scan)
+}
+
+
+#[cfg(test)]
+fn solution2(input: &str) -> Result<usize> {
+    let scan = Day22::parse(input)?;
+    Day22::part2(&scan)
+}
+
+
+// Test the sample puzzle inputs
+// ==================================================
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static SAMPLE: &str = "depth: 510\ntarget: 10,10\n";
+
+    #[test]
+    fn test_samples1() {
+        assert_eq!(solution1(SAMPLE).unwrap(), 114);
+    }
+
+    #[test]
+    fn test_samples2() {
+        assert_eq!(solution2(SAMPLE).unwrap(), 45);
+    }
+}
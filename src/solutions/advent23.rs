@@ -0,0 +1,210 @@
+use std::cmp::Reverse;
+
+use binary_heap_plus::BinaryHeap;
+
+use solutions::day::Day;
+use solutions::error::{Error, Result};
+use solutions::utils;
+
+pub static INPUT: &str = "input23";
+
+#[derive(Debug, PartialEq)]
+pub struct Nanobot {
+    pos: [i64; 3],
+    radius: i64,
+}
+
+impl Nanobot {
+    /// Parse a nanobot like "pos=<0,0,0>, r=4".
+    fn from_str(line: &str) -> Result<Nanobot> {
+        let invalid = || Error::parse(format!("expected a nanobot like 'pos=<0,0,0>, r=4', got '{}'", line));
+        let rest = line.trim().strip_prefix("pos=<").ok_or_else(invalid)?;
+        let end = rest.find(">, r=").ok_or_else(invalid)?;
+
+        let coords = rest[..end].split(',')
+            .map(|coord| utils::parse_num(coord.trim()))
+            .collect::<Result<Vec<i64>>>()?;
+        if coords.len() != 3 {
+            return Err(invalid());
+        }
+
+        Ok(Nanobot {
+            pos: [coords[0], coords[1], coords[2]],
+            radius: utils::parse_num(&rest[end + 5..])?,
+        })
+    }
+
+    fn distance(&self, pos: &[i64; 3]) -> i64 {
+        (0..3).map(|i| (self.pos[i] - pos[i]).abs()).sum()
+    }
+}
+
+/// A cube of `size` along each axis starting at corner `min`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Cube {
+    min: [i64; 3],
+    size: i64,
+}
+
+impl Cube {
+    /// Manhattan distance from a point to the nearest point of the cube.
+    fn distance(&self, pos: &[i64; 3]) -> i64 {
+        (0..3)
+            .map(|i| {
+                let max = self.min[i] + self.size - 1;
+                if pos[i] < self.min[i] {
+                    self.min[i] - pos[i]
+                } else if pos[i] > max {
+                    pos[i] - max
+                } else {
+                    0
+                }
+            })
+            .sum()
+    }
+
+    fn bots_in_range(&self, bots: &[Nanobot]) -> usize {
+        bots.iter().filter(|bot| self.distance(&bot.pos) <= bot.radius).count()
+    }
+
+    fn split(&self) -> Vec<Cube> {
+        let size = self.size / 2;
+        (0..8)
+            .map(|octant| {
+                let mut min = self.min;
+                for (i, coord) in min.iter_mut().enumerate() {
+                    if octant & (1 << i) != 0 {
+                        *coord += size;
+                    }
+                }
+                Cube { min, size }
+            })
+            .collect()
+    }
+}
+
+// Problem 1
+// ==================================================
+
+fn bots_near_strongest(bots: &[Nanobot]) -> usize {
+    let strongest = bots.iter().max_by_key(|bot| bot.radius).unwrap();
+    bots.iter().filter(|bot| strongest.distance(&bot.pos) <= strongest.radius).count()
+}
+
+// Problem 2
+// ==================================================
+
+/// Search cubes that are in range of the most bots first, splitting them
+/// into octants until a single point remains. The bot count of a cube is
+/// an upper bound for all points inside, so the first point found is in
+/// range of the most bots; ties go to the point closest to the origin.
+fn best_position_distance(bots: &[Nanobot]) -> i64 {
+    let extent = bots.iter()
+        .flat_map(|bot| bot.pos.iter().map(move |coord| coord.abs() + bot.radius))
+        .max()
+        .unwrap();
+    let mut size = 1;
+    while size <= extent {
+        size *= 2;
+    }
+
+    let origin = [0; 3];
+    let start = Cube { min: [-size; 3], size: 2 * size };
+    let mut queue = BinaryHeap::new_min();
+    queue.push((Reverse(start.bots_in_range(bots)), start.distance(&origin), start.size, start));
+
+    while let Some((_, distance, size, cube)) = queue.pop() {
+        if size == 1 {
+            return distance;
+        }
+
+        for octant in cube.split() {
+            queue.push((Reverse(octant.bots_in_range(bots)), octant.distance(&origin), octant.size, octant));
+        }
+    }
+
+    unreachable!("the search ends with a single point")
+}
+
+// Interface
+// ==================================================
+
+pub struct Day23;
+
+impl Day for Day23 {
+    type Input = Vec<Nanobot>;
+    type Output1 = usize;
+    type Output2 = i64;
+
+    fn parse(input: &str) -> Result<Vec<Nanobot>> {
+        let bots = utils::parse_lines(input.trim_end(), Nanobot::from_str)?;
+        if bots.is_empty() {
+            return Err(Error::parse("no nanobots in the input"));
+        }
+
+        Ok(bots)
+    }
+
+    fn part1(bots: &Vec<Nanobot>) -> Result<usize> {
+        Ok(bots_near_strongest(bots))
+    }
+
+    fn part2(bots: &Vec<Nanobot>) -> Result<i64> {
+        Ok(best_position_distance(bots))
+    }
+}
+
+
+#[cfg(test)]
+fn solution1(input: &str) -> Result<usize> {
+    let bots = Day23::parse(input)?;
+    Day23::part1(&bots)
+}
+
+
+#[cfg(test)]
+fn solution2(input: &str) -> Result<i64> {
+    let bots = Day23::parse(input)?;
+    Day23::part2(&bots)
+}
+
+
+// Test the sample puzzle inputs
+// ==================================================
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_samples1() {
+        let bots = "pos=<0,0,0>, r=4
+pos=<1,0,0>, r=1
+pos=<4,0,0>, r=3
+pos=<0,2,0>, r=1
+pos=<0,5,0>, r=3
+pos=<0,0,3>, r=1
+pos=<1,1,1>, r=1
+pos=<1,1,2>, r=1
+pos=<1,3,1>, r=1
+";
+        assert_eq!(solution1(bots).unwrap(), 7);
+    }
+
+    #[test]
+    fn test_samples2() {
+        let bots = "pos=<10,12,12>, r=2
+pos=<12,14,12>, r=2
+pos=<16,12,12>, r=4
+pos=<14,14,14>, r=6
+pos=<50,50,50>, r=200
+pos=<10,10,10>, r=5
+";
+        assert_eq!(solution2(bots).unwrap(), 36);
+    }
+
+    #[test]
+    fn test_nanobot() {
+        assert_eq!(Nanobot::from_str("pos=<1,-3,1>, r=12").unwrap(), Nanobot { pos: [1, -3, 1], radius: 12 });
+        assert!(Nanobot::from_str("pos=<1,3>, r=1").is_err());
+    }
+}
@@ -0,0 +1,276 @@
+use solutions::day::Day;
+use solutions::error::{Error, Result};
+use solutions::utils;
+
+pub static INPUT: &str = "input24";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Army {
+    ImmuneSystem,
+    Infection,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Group {
+    army: Army,
+    units: u64,
+    hit_points: u64,
+    weaknesses: Vec<String>,
+    immunities: Vec<String>,
+    damage: u64,
+    attack_type: String,
+    initiative: u64,
+}
+
+impl Group {
+    /// Parse a group like "17 units each with 5390 hit points (weak to
+    /// radiation) with an attack that does 4507 fire damage at initiative 2".
+    fn from_str(army: Army, line: &str) -> Result<Group> {
+        let invalid = || Error::parse(format!("invalid group '{}'", line));
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let mut group = Group {
+            army,
+            units: utils::parse_num(words.first().ok_or_else(invalid)?)?,
+            hit_points: utils::parse_num(words.get(4).ok_or_else(invalid)?)?,
+            weaknesses: Vec::new(),
+            immunities: Vec::new(),
+            damage: 0,
+            attack_type: String::new(),
+            initiative: 0,
+        };
+
+        if let (Some(open), Some(close)) = (line.find('('), line.find(')')) {
+            for part in line[open + 1..close].split("; ") {
+                let (list, types) = if let Some(types) = part.strip_prefix("weak to ") {
+                    (&mut group.weaknesses, types)
+                } else if let Some(types) = part.strip_prefix("immune to ") {
+                    (&mut group.immunities, types)
+                } else {
+                    return Err(invalid());
+                };
+                list.extend(types.split(", ").map(String::from));
+            }
+        }
+
+        let attack = line.find("with an attack that does ").ok_or_else(invalid)?;
+        let words: Vec<&str> = line[attack..].split_whitespace().collect();
+        match words[..] {
+            [_, _, _, _, _, damage, attack_type, "damage", "at", "initiative", initiative] => {
+                group.damage = utils::parse_num(damage)?;
+                group.attack_type = attack_type.to_string();
+                group.initiative = utils::parse_num(initiative)?;
+            }
+            _ => return Err(invalid()),
+        }
+
+        Ok(group)
+    }
+
+    fn effective_power(&self) -> u64 {
+        self.units * self.damage
+    }
+
+    /// Damage this group would deal to the defender.
+    fn damage_to(&self, defender: &Group) -> u64 {
+        if defender.immunities.contains(&self.attack_type) {
+            0
+        } else if defender.weaknesses.contains(&self.attack_type) {
+            2 * self.effective_power()
+        } else {
+            self.effective_power()
+        }
+    }
+}
+
+/// Fight until one army is left. Returns the winner and its remaining
+/// units, or `None` if the fight ends in a stalemate.
+fn fight(groups: &[Group], boost: u64) -> Option<(Army, u64)> {
+    let mut groups: Vec<Group> = groups.iter()
+        .map(|group| match group.army {
+            Army::ImmuneSystem => Group { damage: group.damage + boost, ..group.clone() },
+            Army::Infection => group.clone(),
+        })
+        .collect();
+
+    loop {
+        groups.retain(|group| group.units > 0);
+        let alive = |army| groups.iter().any(|group| group.army == army);
+        match (alive(Army::ImmuneSystem), alive(Army::Infection)) {
+            (true, true) => {}
+            (false, false) => return None,
+            (immune, _) => {
+                let army = if immune { Army::ImmuneSystem } else { Army::Infection };
+                return Some((army, groups.iter().map(|group| group.units).sum()));
+            }
+        }
+
+        // target selection
+        let mut order: Vec<usize> = (0..groups.len()).collect();
+        order.sort_by_key(|&i| (groups[i].effective_power(), groups[i].initiative));
+        order.reverse();
+
+        let mut targets: Vec<Option<usize>> = vec![None; groups.len()];
+        let mut chosen = vec![false; groups.len()];
+        for &attacker in &order {
+            let target = (0..groups.len())
+                .filter(|&defender| groups[defender].army != groups[attacker].army && !chosen[defender])
+                .filter(|&defender| groups[attacker].damage_to(&groups[defender]) > 0)
+                .max_by_key(|&defender| (groups[attacker].damage_to(&groups[defender]),
+                                         groups[defender].effective_power(),
+                                         groups[defender].initiative));
+            if let Some(defender) = target {
+                chosen[defender] = true;
+                targets[attacker] = Some(defender);
+            }
+        }
+
+        // attacking
+        order.sort_by_key(|&i| groups[i].initiative);
+        order.reverse();
+
+        let mut killed_any = false;
+        for &attacker in &order {
+            let defender = match targets[attacker] {
+                Some(defender) if groups[attacker].units > 0 => defender,
+                _ => continue,
+            };
+
+            let killed = (groups[attacker].damage_to(&groups[defender]) / groups[defender].hit_points)
+                .min(groups[defender].units);
+            groups[defender].units -= killed;
+            killed_any |= killed > 0;
+        }
+
+        if !killed_any {
+            return None;
+        }
+    }
+}
+
+// Problem 1
+// ==================================================
+
+fn winning_units(groups: &[Group]) -> Result<u64> {
+    fight(groups, 0)
+        .map(|(_, units)| units)
+        .ok_or_else(|| Error::no_solution("the fight ends in a stalemate"))
+}
+
+// Problem 2
+// ==================================================
+
+fn smallest_boost_units(groups: &[Group]) -> Result<u64> {
+    // with a boost this big every immune group kills any group in one hit
+    let max_boost = groups.iter()
+        .filter(|group| group.army == Army::Infection)
+        .map(|group| group.units * group.hit_points)
+        .sum::<u64>() + 1;
+
+    // the outcome isn't monotone in the boost because of stalemates, so
+    // double the boost until the immune system wins once and then look for
+    // the smallest winning boost below that
+    let immune_wins = |boost| fight(groups, boost).is_some_and(|(army, _)| army == Army::ImmuneSystem);
+    let mut upper = 1;
+    while upper < max_boost && !immune_wins(upper) {
+        upper *= 2;
+    }
+
+    (0..=upper.min(max_boost))
+        .filter_map(|boost| fight(groups, boost))
+        .find(|&(army, _)| army == Army::ImmuneSystem)
+        .map(|(_, units)| units)
+        .ok_or_else(|| Error::no_solution("no boost lets the immune system win"))
+}
+
+// Interface
+// ==================================================
+
+pub struct Day24;
+
+impl Day for Day24 {
+    type Input = Vec<Group>;
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn parse(input: &str) -> Result<Vec<Group>> {
+        let mut groups = Vec::new();
+        let mut army = None;
+
+        for (i, line) in input.lines().enumerate() {
+            match line.trim() {
+                "" => {}
+                "Immune System:" => army = Some(Army::ImmuneSystem),
+                "Infection:" => army = Some(Army::Infection),
+                line => {
+                    let army = army.ok_or_else(|| Error::parse("group before an army heading").at_line(i + 1))?;
+                    groups.push(Group::from_str(army, line).map_err(|err| err.at_line(i + 1))?);
+                }
+            }
+        }
+
+        if groups.iter().any(|group| group.hit_points == 0) {
+            return Err(Error::parse("groups need at least one hit point per unit"));
+        }
+
+        Ok(groups)
+    }
+
+    fn part1(groups: &Vec<Group>) -> Result<u64> {
+        winning_units(groups)
+    }
+
+    fn part2(groups: &Vec<Group>) -> Result<u64> {
+        smallest_boost_units(groups)
+    }
+}
+
+
+#[cfg(test)]
+fn solution1(input: &str) -> Result<u64> {
+    let groups = Day24::parse(input)?;
+    Day24::part1(&groups)
+}
+
+
+#[cfg(test)]
+fn solution2(input: &str) -> Result<u64> {
+    let groups = Day24::parse(input)?;
+    Day24::part2(&groups)
+}
+
+
+// Test the sample puzzle inputs
+// ==================================================
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static SAMPLE: &str = "Immune System:
+17 units each with 5390 hit points (weak to radiation, bludgeoning) with an attack that does 4507 fire damage at initiative 2
+989 units each with 1274 hit points (immune to fire; weak to bludgeoning, slashing) with an attack that does 25 slashing damage at initiative 3
+
+Infection:
+801 units each with 4706 hit points (weak to radiation) with an attack that does 116 bludgeoning damage at initiative 1
+4485 units each with 2961 hit points (immune to radiation; weak to fire, cold) with an attack that does 12 slashing damage at initiative 4
+";
+
+    #[test]
+    fn test_samples1() {
+        assert_eq!(solution1(SAMPLE).unwrap(), 5216);
+    }
+
+    #[test]
+    fn test_samples2() {
+        let groups = Day24::parse(SAMPLE).unwrap();
+        assert_eq!(fight(&groups, 1570), Some((Army::ImmuneSystem, 51)));
+        assert_eq!(solution2(SAMPLE).unwrap(), 51);
+    }
+
+    #[test]
+    fn test_group() {
+        let group = Group::from_str(Army::Infection,
+            "4485 units each with 2961 hit points with an attack that does 12 slashing damage at initiative 4").unwrap();
+        assert_eq!((group.units, group.hit_points, group.damage, group.initiative), (4485, 2961, 12, 4));
+        assert!(group.weaknesses.is_empty() && group.immunities.is_empty());
+    }
+}
@@ -0,0 +1,116 @@
+use solutions::day::Day;
+use solutions::error::{Error, Result};
+use solutions::utils;
+
+pub static INPUT: &str = "input25";
+
+/// Points at most this far apart belong to the same constellation.
+const LINK_DISTANCE: i32 = 3;
+
+type Point = [i32; 4];
+
+fn parse_point(line: &str) -> Result<Point> {
+    let coords = line.split(',')
+        .map(|coord| utils::parse_num(coord.trim()))
+        .collect::<Result<Vec<i32>>>()?;
+    if coords.len() != 4 {
+        return Err(Error::parse(format!("expected four coordinates, got '{}'", line.trim())));
+    }
+
+    Ok([coords[0], coords[1], coords[2], coords[3]])
+}
+
+fn distance(a: &Point, b: &Point) -> i32 {
+    a.iter().zip(b.iter()).map(|(a, b)| (a - b).abs()).sum()
+}
+
+/// Union-find root of a point, compressing the path on the way.
+fn find(parents: &mut [usize], point: usize) -> usize {
+    let mut root = point;
+    while parents[root] != root {
+        root = parents[root];
+    }
+
+    let mut point = point;
+    while parents[point] != root {
+        let next = parents[point];
+        parents[point] = root;
+        point = next;
+    }
+
+    root
+}
+
+// Problem 1
+// ==================================================
+
+fn count_constellations(points: &[Point]) -> usize {
+    let mut parents: Vec<usize> = (0..points.len()).collect();
+    for i in 0..points.len() {
+        for j in i + 1..points.len() {
+            if distance(&points[i], &points[j]) <= LINK_DISTANCE {
+                let (a, b) = (find(&mut parents, i), find(&mut parents, j));
+                parents[a] = b;
+            }
+        }
+    }
+
+    (0..points.len()).filter(|&i| find(&mut parents, i) == i).count()
+}
+
+// Problem 2
+// ==================================================
+
+// The last star is earned by collecting all the others, there is nothing
+// left to compute.
+const LAST_STAR: &str = "Merry Christmas!";
+
+// Interface
+// ==================================================
+
+pub struct Day25;
+
+impl Day for Day25 {
+    type Input = Vec<Point>;
+    type Output1 = usize;
+    type Output2 = &'static str;
+
+    fn parse(input: &str) -> Result<Vec<Point>> {
+        utils::parse_lines(input.trim_end(), parse_point)
+    }
+
+    fn part1(points: &Vec<Point>) -> Result<usize> {
+        Ok(count_constellations(points))
+    }
+
+    fn part2(_points: &Vec<Point>) -> Result<&'static str> {
+        Ok(LAST_STAR)
+    }
+}
+
+
+#[cfg(test)]
+fn solution1(input: &str) -> Result<usize> {
+    let points = Day25::parse(input)?;
+    Day25::part1(&points)
+}
+
+
+// Test the sample puzzle inputs
+// ==================================================
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_samples1() {
+        let samples = [
+            "0,0,0,0\n3,0,0,0\n0,3,0,0\n0,0,3,0\n0,0,0,3\n0,0,0,6\n9,0,0,0\n12,0,0,0\n",
+            "-1,2,2,0\n0,0,2,-2\n0,0,0,-2\n-1,2,0,0\n-2,-2,-2,2\n3,0,2,-1\n-1,3,2,2\n-1,0,-1,0\n0,2,1,-2\n3,0,0,0\n",
+            "1,-1,0,1\n2,0,-1,0\n3,2,-1,0\n0,0,3,1\n0,0,-1,-1\n2,3,-2,0\n-2,2,0,0\n2,-2,0,-1\n1,-1,0,-1\n3,2,0,2\n",
+            "1,-1,-1,-2\n-2,-2,0,1\n0,2,1,3\n-2,3,-2,1\n0,2,3,-2\n-1,-1,1,-2\n0,-2,-1,0\n-2,2,3,-1\n1,2,2,0\n-1,-2,0,-2\n",
+        ];
+        let counts: Vec<usize> = samples.iter().map(|sample| solution1(sample).unwrap()).collect();
+        assert_eq!(counts, vec![2, 4, 3, 8]);
+    }
+}
@@ -1,11 +1,19 @@
+use std::io::BufRead;
+
+use solutions::day::Day;
+use solutions::error::{Error, Result};
 use solutions::utils;
 
-static INPUT: &str = "data/input3";
+pub static INPUT: &str = "input3";
+
+/// The fabric is about 1000 inches wide; claims reaching beyond this many
+/// inches are rejected instead of allocating a huge cover matrix.
+const MAX_FABRIC_SIZE: u32 = 2_000;
 
 // Problem 1
 // ==================================================
 
-struct Square {
+pub struct Square {
     id: String,
     x: u32,
     y: u32,
@@ -13,27 +21,53 @@ struct Square {
     h: u32,
 }
 
+/// Split something like "3,2" into its two numbers.
+fn parse_pair(field: &str, separator: char) -> Result<(u32, u32)> {
+    let mut values = field.split(separator);
+    match (values.next(), values.next(), values.next()) {
+        (Some(a), Some(b), None) => Ok((utils::parse_num(a)?, utils::parse_num(b)?)),
+        _ => Err(Error::parse(format!("expected two numbers separated by '{}' in '{}'",
+                                      separator, field))),
+    }
+}
+
 impl Square {
     /// Constructs a new square from a claim string
     /// as given by the AOC problem: "#ID @ x,y: wxh"
-    pub fn from_claim_str(claim: &str) -> Square {
+    pub fn from_claim_str(claim: &str) -> Result<Square> {
         let claim_fields: Vec<&str> = claim.split_whitespace().collect();
-        let id = String::from(claim_fields[0]);
+        if claim_fields.len() != 4 || !claim_fields[0].starts_with('#') ||
+            claim_fields[1] != "@" || !claim_fields[2].ends_with(':') {
+            return Err(Error::parse(format!("expected a claim like '#1 @ 3,2: 5x4', got '{}'",
+                                            claim)));
+        }
+
+        let id = String::from(claim_fields[0].trim_start_matches('#'));
         let pos = claim_fields[2];
         let pos = &pos[..(pos.len() - 1)];
         let dim = claim_fields[3];
 
-        let tmp: Vec<u32> = pos.split(',').map(|s| s.parse::<u32>().unwrap()).collect();
-        let (x, y) = (tmp[0], tmp[1]);
-        let tmp: Vec<u32> = dim.split('x').map(|s| s.parse::<u32>().unwrap()).collect();
-        let (w, h) = (tmp[0], tmp[1]);
+        let (x, y) = parse_pair(pos, ',')?;
+        let (w, h) = parse_pair(dim, 'x')?;
 
-        Square {id, x, y, w, h }
+        let fits = |start: u32, size: u32| {
+            start.checked_add(size).is_some_and(|end| end <= MAX_FABRIC_SIZE)
+        };
+        if !fits(x, w) || !fits(y, h) {
+            return Err(Error::parse(format!("claim '{}' reaches beyond {} inches of fabric",
+                                            claim, MAX_FABRIC_SIZE)));
+        }
+
+        Ok(Square {id, x, y, w, h })
     }
 }
 
-fn compute_square_cover(squares: &Vec<Square>) -> Vec<Vec<u32>> {
-    let mut count_matrix: Vec<Vec<u32>> = vec![vec![0; 1000]; 1000];
+fn compute_square_cover(squares: &[Square]) -> Vec<Vec<u32>> {
+    // the fabric is at least 1000 inches wide, but we make room for larger claims;
+    // parsing keeps every claim within MAX_FABRIC_SIZE
+    let width = squares.iter().map(|square| square.x + square.w).max().unwrap_or(0).max(1000);
+    let height = squares.iter().map(|square| square.y + square.h).max().unwrap_or(0).max(1000);
+    let mut count_matrix: Vec<Vec<u32>> = vec![vec![0; height as usize]; width as usize];
 
     for square in squares {
         for x in (square.x)..(square.x + square.w) {
@@ -46,8 +80,8 @@ fn compute_square_cover(squares: &Vec<Square>) -> Vec<Vec<u32>> {
     count_matrix
 }
 
-fn count_duplicates_squares(squares: Vec<Square>) -> u32 {
-    let count_matrix = compute_square_cover(&squares);
+fn count_duplicates_squares(squares: &[Square]) -> u32 {
+    let count_matrix = compute_square_cover(squares);
     let val_count = count_matrix.iter()
         .flatten()
         .map(|&count| (count > 1) as u32)
@@ -59,8 +93,8 @@ fn count_duplicates_squares(squares: Vec<Square>) -> u32 {
 // Problem 2
 // ==================================================
 
-fn get_intact_square(squares: Vec<Square>) -> String {
-    let count_matrix = compute_square_cover(&squares);
+fn get_intact_square(squares: &[Square]) -> Result<String> {
+    let count_matrix = compute_square_cover(squares);
 
     let mut found_square = None;
     'start: for square in squares {
@@ -77,39 +111,50 @@ fn get_intact_square(squares: Vec<Square>) -> String {
         break;
     }
 
-    // Unwrap should succeed because the input has one right answer
-    found_square.unwrap().id
+    found_square
+        .map(|square| square.id.clone())
+        .ok_or_else(|| Error::no_solution("every claim overlaps another claim"))
 }
 
 // Interface
 // ==================================================
 
-pub fn solution1() -> u32 {
-    let file_input = utils::file_to_string(INPUT);
-    let squares: Vec<_> = file_input.lines()
-        .map(|line| Square::from_claim_str(line))
-        .collect();
+pub struct Day3;
 
-    count_duplicates_squares(squares)
-}
+impl Day for Day3 {
+    type Input = Vec<Square>;
+    type Output1 = u32;
+    type Output2 = String;
 
+    fn parse(input: &str) -> Result<Vec<Square>> {
+        utils::parse_lines(input, Square::from_claim_str)
+    }
 
-pub fn solution2() -> String {
-    let file_input = utils::file_to_string(INPUT);
-    let squares: Vec<_> = file_input.lines()
-        .map(|line| Square::from_claim_str(line))
-        .collect();
+    fn parse_reader<R: BufRead>(reader: R) -> Result<Vec<Square>> {
+        utils::parse_reader_lines(reader, Square::from_claim_str)
+    }
 
-    get_intact_square(squares)
+    fn part1(squares: &Vec<Square>) -> Result<u32> {
+        Ok(count_duplicates_squares(squares))
+    }
+
+    fn part2(squares: &Vec<Square>) -> Result<String> {
+        get_intact_square(squares)
+    }
 }
 
 
-pub fn solve_day() {
-    let solution1 = solution1();
-    println!("Covered squares: {}", solution1);
+#[cfg(test)]
+fn solution1(input: &str) -> Result<u32> {
+    let squares = Day3::parse(input)?;
+    Day3::part1(&squares)
+}
+
 
-    let solution2 = solution2();
-    println!("Intact claim: {}", solution2);
+#[cfg(test)]
+fn solution2(input: &str) -> Result<String> {
+    let squares = Day3::parse(input)?;
+    Day3::part2(&squares)
 }
 
 
@@ -119,20 +164,42 @@ pub fn solve_day() {
 mod test {
     use super::*;
 
+    static CLAIMS: &str = "#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2\n";
+
     #[test]
-    fn test_samples1() {}
+    fn test_samples1() {
+        assert_eq!(solution1(CLAIMS).unwrap(), 4);
+    }
 
     #[test]
-    fn test_samples2() {}
+    fn test_samples2() {
+        assert_eq!(solution2(CLAIMS).unwrap(), "3");
+    }
 
     #[test]
     fn test_square_from_claim() {
         let test_str = "#123 @ 3,2: 5x4";
-        let square = Square::from_claim_str(test_str);
+        let square = Square::from_claim_str(test_str).unwrap();
 
         assert_eq!(square.x, 3);
         assert_eq!(square.y, 2);
         assert_eq!(square.w, 5);
         assert_eq!(square.h, 4);
     }
+
+    #[test]
+    fn test_bad_claim() {
+        let err = Day3::parse("#1 @ 3,2: 5x4\n#2 @ 3,2 5x4").err().unwrap();
+        assert_eq!(err.to_string(),
+                   "line 2: expected a claim like '#1 @ 3,2: 5x4', got '#2 @ 3,2 5x4'");
+
+        let err = Day3::parse("#1 @ 3,2: 5xy").err().unwrap();
+        assert_eq!(err.to_string(), "line 1: invalid number 'y': invalid digit found in string");
+
+        let err = Day3::parse("#1 @ 3,2: 5x4\n#2 @ 4294967295,1: 2x2").err().unwrap();
+        assert_eq!(err.to_string(),
+                   "line 2: claim '#2 @ 4294967295,1: 2x2' reaches beyond 2000 inches of fabric");
+        assert!(Day3::parse("#1 @ 1,1999: 1x2").is_err());
+        assert!(Day3::parse("#1 @ 1,1998: 1x2").is_ok());
+    }
 }
@@ -1,67 +1,86 @@
 use std::collections::HashMap;
+use std::io::BufRead;
 
 use chrono::{NaiveDateTime, Timelike};
 
+use solutions::day::Day;
+use solutions::error::{Error, Result};
 use solutions::utils;
 
-static INPUT: &str = "data/input4";
+pub static INPUT: &str = "input4";
 
 // Problem 1
 // =================================================
 
 #[derive(Debug)]
 enum LineAction {
-    GuardStarts(String),
+    GuardStarts(u32),
     Sleep,
     Wake,
 }
 
-fn split_components(line: &str) -> (NaiveDateTime, LineAction) {
+fn split_components(line: &str) -> Result<(NaiveDateTime, LineAction)> {
     // split something like "[1518-05-12 00:46] wakes up"
     // first part will be "[1518-05-12 00:46", so cut off start
     let mut linesplit = line.split(']');
     let timestamp_string = linesplit.next().unwrap();
+    if !timestamp_string.starts_with('[') {
+        return Err(Error::parse(format!("expected a '[timestamp]' at the start of '{}'", line)));
+    }
+
     let timestamp_string: &str = &timestamp_string[1..];
-    let timestamp = NaiveDateTime::parse_from_str(timestamp_string,
-                                                  "%Y-%m-%d %H:%M").unwrap();
+    let timestamp = NaiveDateTime::parse_from_str(timestamp_string, "%Y-%m-%d %H:%M")
+        .map_err(|err| Error::parse(format!("invalid timestamp '{}': {}", timestamp_string, err)))?;
 
-    let guard_action: &str = linesplit.next().unwrap();
+    let guard_action: &str = linesplit.next()
+        .ok_or_else(|| Error::parse(format!("missing guard action in '{}'", line)))?;
     let line_action =
         if guard_action.contains("asleep") {
             LineAction::Sleep
         } else if guard_action.contains("wakes") {
             LineAction::Wake
         } else {
-            let guard_id: Vec<_> = guard_action.split_whitespace().collect();
-            let guard_id = guard_id[1];
-            LineAction::GuardStarts(String::from(guard_id))
+            // "Guard #10 begins shift"
+            let guard_id = guard_action.split_whitespace()
+                .nth(1)
+                .filter(|guard_id| guard_id.starts_with('#'))
+                .ok_or_else(|| Error::parse(format!("unknown guard action '{}'",
+                                                    guard_action.trim())))?;
+            LineAction::GuardStarts(utils::parse_num(&guard_id[1..])?)
         };
 
-    (timestamp, line_action)
+    Ok((timestamp, line_action))
 }
 
-fn split_and_sort(lines: Vec<&str>) -> Vec<(NaiveDateTime, LineAction)> {
-    let mut line_actions: Vec<_> = lines.iter().map(|line| split_components(line)).collect();
+fn split_and_sort<R: BufRead>(reader: R) -> Result<Vec<(NaiveDateTime, LineAction)>> {
+    let mut line_actions = utils::parse_reader_lines(reader, split_components)?;
     line_actions.sort_by_key(|&(timestamp, _)| timestamp);
 
-    line_actions
+    Ok(line_actions)
 }
 
 
-fn count_sleeps(line_actions: &Vec<(NaiveDateTime, LineAction)>) -> HashMap<String, Vec<u32>> {
-    let mut sleep_counter: HashMap<String, Vec<u32>> = HashMap::new();
+fn count_sleeps(line_actions: &[(NaiveDateTime, LineAction)]) -> Result<HashMap<u32, Vec<u32>>> {
+    let mut sleep_counter: HashMap<u32, Vec<u32>> = HashMap::new();
 
-    let mut active_guard: Option<&str> = None;
+    let mut active_guard: Option<u32> = None;
     let mut asleep_time: Option<&NaiveDateTime> = None;
     for (time, action) in line_actions {
         match action {
             LineAction::GuardStarts(guard_id) => {
-                active_guard = Some(guard_id);
+                active_guard = Some(*guard_id);
+                asleep_time = None;
             }
             LineAction::Wake => {
-                let sleep_start = asleep_time.unwrap().minute();
+                let guard_id = active_guard.ok_or_else(|| {
+                    Error::parse(format!("someone wakes up at {} before any guard is on duty", time))
+                })?;
+                let sleep_start = asleep_time.take().ok_or_else(|| {
+                    Error::parse(format!("guard #{} wakes up at {} without falling asleep",
+                                         guard_id, time))
+                })?.minute();
                 let sleep_end = time.minute() + 1;
-                let guard_counter = sleep_counter.entry(active_guard.unwrap().to_string())
+                let guard_counter = sleep_counter.entry(guard_id)
                     .or_insert(vec![0; 60]);
 
                 for minute in sleep_start..sleep_end {
@@ -74,62 +93,87 @@ fn count_sleeps(line_actions: &Vec<(NaiveDateTime, LineAction)>) -> HashMap<Stri
         };
     }
 
-    sleep_counter
+    Ok(sleep_counter)
 }
 
-// Problem 2
-// ==================================================
-
-// Interface
-// ==================================================
-
-pub fn solution1() -> () {
-    let input = utils::file_to_string(INPUT);
-    let sorted_actions = split_and_sort(input.lines().collect());
-    let sleep_counter = count_sleeps(&sorted_actions);
-
-    let max_total_sleep_counter: (&String, &Vec<u32>) = sleep_counter
+/// Find the guard that sleeps most in total and multiply
+/// their ID with the minute they are most often asleep.
+fn sleepiest_guard_checksum(sleep_counter: &HashMap<u32, Vec<u32>>) -> Result<u32> {
+    let (guard_id, minutes) = sleep_counter
         .iter()
         .max_by_key(|(_, v)| -> u32 {
             v.iter().sum()
-        }).unwrap();
+        }).ok_or_else(|| Error::no_solution("no guard ever falls asleep"))?;
 
-    let max_sleep = max_total_sleep_counter.1.iter().max().unwrap();
-    let max_sleep_index = max_total_sleep_counter.1.iter()
+    let max_sleep = minutes.iter().max().unwrap();
+    let max_sleep_index = minutes.iter()
         .position(|m| m == max_sleep).unwrap();
-    let sleep_sum = max_total_sleep_counter.1.iter().sum::<u32>();
 
-    println!("Guard with maximum sleep time:\n{} - Sleep minutes: {} - Sleepiest minute: {}",
-             max_total_sleep_counter.0, sleep_sum, max_sleep_index);
+    Ok(guard_id * max_sleep_index as u32)
 }
 
+// Problem 2
+// ==================================================
 
-pub fn solution2() -> () {
-    let input = utils::file_to_string(INPUT);
-    let sorted_actions = split_and_sort(input.lines().collect());
-    let sleep_counter = count_sleeps(&sorted_actions);
-
-    let max_sleep_minute_counter = sleep_counter
+/// Find the guard that is most frequently asleep on the same minute
+/// and multiply their ID with that minute.
+fn sleepiest_minute_checksum(sleep_counter: &HashMap<u32, Vec<u32>>) -> Result<u32> {
+    let (guard_id, minutes) = sleep_counter
         .iter()
         .max_by_key(|(_, v)| {
             v.iter().max().unwrap()
-    }).unwrap();
+    }).ok_or_else(|| Error::no_solution("no guard ever falls asleep"))?;
 
-    let max_sleep = max_sleep_minute_counter.1.iter().max().unwrap();
-    let max_sleep_index = max_sleep_minute_counter.1.iter()
+    let max_sleep = minutes.iter().max().unwrap();
+    let max_sleep_index = minutes.iter()
         .position(|m| m == max_sleep).unwrap();
 
-    println!("Guard with sleepiest minute:\n{} - Minute: {} - Days asleep: {}",
-             max_sleep_minute_counter.0, max_sleep_index, max_sleep);
-
+    Ok(guard_id * max_sleep_index as u32)
 }
 
+// Interface
+// ==================================================
+
 /// Problem 1 and problem 2 are pretty much identical on this day,
 /// only that problem 2 uses a sleepy minute counting for maximum
 /// columns (sleep minutes) instead of maximum rows (minutes asleep).
-pub fn solve_day() {
-    solution1();
-    solution2();
+pub struct Day4;
+
+impl Day for Day4 {
+    type Input = HashMap<u32, Vec<u32>>;
+    type Output1 = u32;
+    type Output2 = u32;
+
+    fn parse(input: &str) -> Result<HashMap<u32, Vec<u32>>> {
+        Day4::parse_reader(input.as_bytes())
+    }
+
+    fn parse_reader<R: BufRead>(reader: R) -> Result<HashMap<u32, Vec<u32>>> {
+        let sorted_actions = split_and_sort(reader)?;
+        count_sleeps(&sorted_actions)
+    }
+
+    fn part1(sleep_counter: &HashMap<u32, Vec<u32>>) -> Result<u32> {
+        sleepiest_guard_checksum(sleep_counter)
+    }
+
+    fn part2(sleep_counter: &HashMap<u32, Vec<u32>>) -> Result<u32> {
+        sleepiest_minute_checksum(sleep_counter)
+    }
+}
+
+
+#[cfg(test)]
+fn solution1(input: &str) -> Result<u32> {
+    let sleep_counter = Day4::parse(input)?;
+    Day4::part1(&sleep_counter)
+}
+
+
+#[cfg(test)]
+fn solution2(input: &str) -> Result<u32> {
+    let sleep_counter = Day4::parse(input)?;
+    Day4::part2(&sleep_counter)
 }
 
 
@@ -137,9 +181,53 @@ pub fn solve_day() {
 // ================================================== 
 #[cfg(test)]
 mod test {
+    use super::*;
+
+    static GUARD_LOG: &str = "\
+[1518-11-01 00:00] Guard #10 begins shift
+[1518-11-01 00:05] falls asleep
+[1518-11-01 00:25] wakes up
+[1518-11-01 00:30] falls asleep
+[1518-11-01 00:55] wakes up
+[1518-11-01 23:58] Guard #99 begins shift
+[1518-11-02 00:40] falls asleep
+[1518-11-02 00:50] wakes up
+[1518-11-03 00:05] Guard #10 begins shift
+[1518-11-03 00:24] falls asleep
+[1518-11-03 00:29] wakes up
+[1518-11-04 00:02] Guard #99 begins shift
+[1518-11-04 00:36] falls asleep
+[1518-11-04 00:46] wakes up
+[1518-11-05 00:03] Guard #99 begins shift
+[1518-11-05 00:45] falls asleep
+[1518-11-05 00:55] wakes up";
+
+    #[test]
+    fn test_samples1() {
+        assert_eq!(solution1(GUARD_LOG).unwrap(), 240);
+    }
+
     #[test]
-    fn test_samples1() {}
+    fn test_samples2() {
+        assert_eq!(solution2(GUARD_LOG).unwrap(), 4455);
+    }
 
     #[test]
-    fn test_samples2() {}
+    fn test_unsorted_log() {
+        let mut lines: Vec<&str> = GUARD_LOG.lines().collect();
+        lines.reverse();
+        assert_eq!(solution1(&lines.join("\n")).unwrap(), 240);
+    }
+
+    #[test]
+    fn test_bad_lines() {
+        let err = Day4::parse("[1518-11-01 00:00] Guard #10 begins shift\n\
+                               [1518-11-01 00:05] dozes off").err().unwrap();
+        assert_eq!(err.to_string(), "line 2: unknown guard action 'dozes off'");
+
+        let err = Day4::parse("[1518-11-01 00:00] Guard #10 begins shift\n\
+                               [1518-11-01 00:25] wakes up").err().unwrap();
+        assert_eq!(err.to_string(),
+                   "invalid input: guard #10 wakes up at 1518-11-01 00:25:00 without falling asleep");
+    }
 }
@@ -1,90 +1,180 @@
-use std::collections::HashSet;
+use std::collections::BTreeSet;
+use std::io::BufRead;
+use std::thread;
 
-use solutions::utils;
+use solutions::day::Day;
+use solutions::error::{Error, Result};
 
-static INPUT: &str = "data/input5";
+pub static INPUT: &str = "input5";
 
 // Problem 1
 // ==================================================
 
-fn reduce_polymer(polymer: &str) -> String {
-    let mut reduced = Vec::new();
-
-    for c in polymer.chars() {
-        match reduced.last().cloned() {
-            Some(ref last_c) => {
-                if (c.is_ascii_uppercase() && (c.to_ascii_lowercase() == *last_c)) ||
-                    (c.is_ascii_lowercase() && (c.to_ascii_uppercase() == *last_c)) {
-                    reduced.pop().unwrap();
-                } else {
-                    reduced.push(c);
-                }
-            }
-            None => {
-                reduced.push(c)
-            }
-        };
+/// Whether two units are the same type with opposite polarity, like `a` and `A`.
+fn reacts(a: u8, b: u8) -> bool {
+    a ^ b == b'a' ^ b'A'
+}
+
+/// Reduce the polymer into `reduced`, leaving out all units of the
+/// (uppercase) type `skip`. `reduced` is cleared first, so one buffer
+/// can be reused for many reductions.
+fn reduce_into(polymer: &[u8], skip: Option<u8>, reduced: &mut Vec<u8>) {
+    reduced.clear();
+
+    for &unit in polymer {
+        if Some(unit.to_ascii_uppercase()) == skip {
+            continue;
+        }
+
+        if reduced.last().is_some_and(|&last| reacts(last, unit)) {
+            reduced.pop();
+        } else {
+            reduced.push(unit);
+        }
     }
+}
 
-    reduced.into_iter().collect()
+fn reduce_polymer(polymer: &[u8]) -> Vec<u8> {
+    let mut reduced = Vec::with_capacity(polymer.len());
+    reduce_into(polymer, None, &mut reduced);
+    reduced
 }
 
 // Problem 2
 // ==================================================
 
-fn filtered_polymers(polymer: String) -> Vec<(char, String)> {
-    let character_set = polymer
-        .chars()
-        .map(|c| c.to_ascii_uppercase())
-        .collect::<HashSet<char>>();
+/// The (uppercase) unit types that appear in the polymer.
+fn unit_types(polymer: &[u8]) -> Vec<u8> {
+    polymer.iter()
+        .map(|unit| unit.to_ascii_uppercase())
+        .collect::<BTreeSet<u8>>()
+        .into_iter()
+        .collect()
+}
+
+/// The polymer left after removing one unit type and reducing.
+#[derive(Debug, PartialEq)]
+pub struct Removal {
+    /// The removed unit type, in uppercase.
+    pub unit_type: char,
+    pub length: usize,
+    pub polymer: Vec<u8>,
+}
 
-    let filtered_polys = character_set.iter().map(|ref_c| {
-        let filtered_polymer = polymer
-            .chars()
-            .filter(|c| c.to_ascii_uppercase() != *ref_c)
-            .collect::<String>();
+/// Reduce the polymer without each of the unit types and summarize every
+/// reduced polymer, with the unit types spread over up to `threads` threads.
+/// Each thread reuses one buffer for its reductions.
+fn filtered_reductions<T, F>(polymer: &[u8], unit_types: &[u8], threads: usize, summarize: F) -> Vec<T>
+where
+    T: Send,
+    F: Fn(u8, &[u8]) -> T + Sync,
+{
+    let chunk_size = unit_types.len().div_ceil(threads.max(1)).max(1);
+    let summarize = &summarize;
+
+    thread::scope(|scope| {
+        let workers = unit_types.chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || {
+                let mut reduced = Vec::with_capacity(polymer.len());
+                chunk.iter()
+                    .map(|&unit_type| {
+                        reduce_into(polymer, Some(unit_type), &mut reduced);
+                        summarize(unit_type, &reduced)
+                    })
+                    .collect::<Vec<_>>()
+            }))
+            .collect::<Vec<_>>();
+
+        workers.into_iter()
+            .flat_map(|worker| worker.join().unwrap())
+            .collect()
+    })
+}
 
-            (*ref_c, filtered_polymer)
-    }).collect::<Vec<_>>();
+/// `filtered_reductions` for every unit type of the polymer, on all threads.
+fn reduce_without_each<T, F>(polymer: &[u8], summarize: F) -> Result<Vec<T>>
+where
+    T: Send,
+    F: Fn(u8, &[u8]) -> T + Sync,
+{
+    let unit_types = unit_types(polymer);
+    if unit_types.is_empty() {
+        return Err(Error::no_solution("the polymer has no units to remove"));
+    }
 
-    filtered_polys
+    // removing a unit type and reducing gives the same polymer whether or
+    // not the polymer was reduced before, so start from the reduced one
+    let reduced = reduce_polymer(polymer);
+    let threads = thread::available_parallelism().map_or(1, usize::from);
+    Ok(filtered_reductions(&reduced, &unit_types, threads, summarize))
+}
+
+/// Length of the shortest polymer we can get by removing one unit type
+/// before reducing, together with the removed (uppercase) unit type.
+/// Ties go to the first unit type.
+fn shortest_filtered_polymer(polymer: &[u8]) -> Result<(char, usize)> {
+    let lengths = reduce_without_each(polymer, |unit_type, reduced| (reduced.len(), unit_type))?;
+    let (length, unit_type) = lengths.into_iter().min().unwrap();
+    Ok((unit_type as char, length))
+}
+
+/// The removal of every unit type of the polymer, shortest reduced polymer
+/// first. Ties are ordered by unit type.
+pub fn rank_removals(polymer: &[u8]) -> Result<Vec<Removal>> {
+    let mut ranking = reduce_without_each(polymer, |unit_type, reduced| {
+        Removal { unit_type: unit_type as char, length: reduced.len(), polymer: reduced.to_vec() }
+    })?;
+
+    ranking.sort_by_key(|removal| (removal.length, removal.unit_type));
+    Ok(ranking)
 }
 
 // Interface
 // ==================================================
 
-pub fn solution1() -> () {
-    let base_polymer = utils::file_to_string(INPUT);
-    let base_polymer = base_polymer.trim_right();
-    let reduced_polymer = reduce_polymer(base_polymer);
-    println!("Reduced polymer has length {}:\n{}", reduced_polymer.len(), reduced_polymer);
-}
+pub struct Day5;
+
+impl Day for Day5 {
+    type Input = Vec<u8>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<u8>> {
+        Day5::parse_reader(input.as_bytes())
+    }
+
+    /// The polymer is read as bytes, units are ASCII letters.
+    fn parse_reader<R: BufRead>(mut reader: R) -> Result<Vec<u8>> {
+        let mut polymer = Vec::new();
+        reader.read_to_end(&mut polymer).map_err(|err| Error::Io(String::from("input"), err))?;
+        while polymer.last().is_some_and(u8::is_ascii_whitespace) {
+            polymer.pop();
+        }
+
+        if let Some(pos) = polymer.iter().position(|unit| !unit.is_ascii_alphabetic()) {
+            let unit = String::from_utf8_lossy(&polymer[pos..]).chars().next().unwrap();
+            return Err(Error::parse(format!("invalid unit {:?} at position {}", unit, pos + 1))
+                .at_line(1));
+        }
+
+        Ok(polymer)
+    }
 
+    fn part1(polymer: &Vec<u8>) -> Result<usize> {
+        Ok(reduce_polymer(polymer).len())
+    }
 
-pub fn solution2() -> () {
-    let base_polymer = utils::file_to_string(INPUT);
-    let base_polymer = base_polymer.trim_right().to_owned();
-    let filtered_polys = filtered_polymers(base_polymer);
-    let char_filter_lens = filtered_polys
-        .iter()
-        .map(|(c, filtered_poly)| {
-            let reduced_filtered_poly = reduce_polymer(&filtered_poly);
-            (c, reduced_filtered_poly.len())
-        })
-        .collect::<Vec<_>>();
-
-    let (filter_char, filter_len) = char_filter_lens
-        .iter()
-        .min_by_key(|(_, filter_len)| filter_len).unwrap();
-
-    println!("Smallest length of {} was after filtering '{}/{}'!",
-             filter_len, filter_char, filter_char.to_ascii_lowercase());
+    fn part2(polymer: &Vec<u8>) -> Result<usize> {
+        Ok(shortest_filtered_polymer(polymer)?.1)
+    }
 }
 
 
-pub fn solve_day() {
-    solution1();
-    solution2();
+/// All unit types ranked by the length of the polymer without them.
+#[cfg(test)]
+fn solution2(input: &str) -> Result<Vec<Removal>> {
+    let polymer = Day5::parse(input)?;
+    rank_removals(&polymer)
 }
 
 
@@ -96,23 +186,70 @@ mod test {
 
     #[test]
     fn test_samples1() {
-        let sample1 = "abBA";
-        assert_eq!(reduce_polymer(sample1), "");
+        let sample1 = b"abBA";
+        assert_eq!(reduce_polymer(sample1), b"");
 
-        let sample2 = "abAB";
-        assert_eq!(reduce_polymer(sample2), "abAB");
+        let sample2 = b"abAB";
+        assert_eq!(reduce_polymer(sample2), b"abAB");
 
-        let sample3 = "aabAAB";
-        assert_eq!(reduce_polymer(sample3), "aabAAB");
+        let sample3 = b"aabAAB";
+        assert_eq!(reduce_polymer(sample3), b"aabAAB");
 
-        let sample4 = "dabAcCaCBAcCcaDA";
-        assert_eq!(reduce_polymer(sample4), "dabCBAcaDA");
+        let sample4 = b"dabAcCaCBAcCcaDA";
+        assert_eq!(reduce_polymer(sample4), b"dabCBAcaDA");
 
-        let sample5 = "lsCTtcCctTuthHhHzEVLlmMtmTJjFfFfJmMtTdDOfkawnNIilLYyXxHHhfpPYyZmMDVvtTcCdR";
-        let sample5_result = "lsutzEVtmTJOfkawHfZR";
+        let sample5 = b"lsCTtcCctTuthHhHzEVLlmMtmTJjFfFfJmMtTdDOfkawnNIilLYyXxHHhfpPYyZmMDVvtTcCdR";
+        let sample5_result = b"lsutzEVtmTJOfkawHfZR";
         assert_eq!(reduce_polymer(sample5), sample5_result);
     }
 
     #[test]
-    fn test_samples2() {}
+    fn test_samples2() {
+        let ranking = solution2("dabAcCaCBAcCcaDA\n").unwrap();
+        let ranked = ranking.iter()
+            .map(|removal| (removal.unit_type, removal.length, &removal.polymer[..]))
+            .collect::<Vec<_>>();
+        assert_eq!(ranked, vec![('C', 4, &b"daDA"[..]), ('A', 6, b"dbCBcD"), ('D', 6, b"abCBAc"),
+                                ('B', 8, b"daCAcaDA")]);
+    }
+
+    #[test]
+    fn test_filtered_reductions() {
+        let polymer = b"dabAcCaCBAcCcaDA";
+        let lengths = |polymer: &[u8], threads| {
+            filtered_reductions(polymer, b"ABCD", threads, |unit_type, reduced| {
+                (unit_type as char, reduced.len())
+            })
+        };
+        assert_eq!(unit_types(polymer), b"ABCD");
+
+        for threads in 1..6 {
+            let expected = vec![('A', 6), ('B', 8), ('C', 4), ('D', 6)];
+            assert_eq!(lengths(polymer, threads), expected);
+            assert_eq!(lengths(&reduce_polymer(polymer), threads), expected);
+        }
+    }
+
+    #[test]
+    fn test_shortest_filtered_polymer() {
+        let polymer = b"dabAcCaCBAcCcaDA";
+        assert_eq!(shortest_filtered_polymer(polymer).unwrap(), ('C', 4));
+
+        // ties go to the first unit type, as in the ranking
+        let ranking = rank_removals(b"aAbBcdCD").unwrap();
+        let first = (ranking[0].unit_type, ranking[0].length);
+        assert_eq!(shortest_filtered_polymer(b"aAbBcdCD").unwrap(), first);
+        assert!(shortest_filtered_polymer(b"").is_err());
+    }
+
+    #[test]
+    fn test_no_units() {
+        assert!(solution2("\n").is_err());
+    }
+
+    #[test]
+    fn test_bad_polymer() {
+        let err = Day5::parse("dabAc-CaCBAcCcaDA\n").err().unwrap();
+        assert_eq!(err.to_string(), "line 1: invalid unit '-' at position 6");
+    }
 }
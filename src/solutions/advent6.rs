@@ -1,48 +1,50 @@
 use std::collections::HashMap;
+use std::io::BufRead;
 
+use solutions::day::Day;
+use solutions::error::{Error, Result};
 use solutions::utils;
 
-static INPUT: &str = "data/input6";
+pub static INPUT: &str = "input6";
+
+/// Locations of the safe region have a total distance below this.
+const SAFE_DISTANCE: u32 = 10000;
 
 #[derive(Debug, Copy, Clone)]
-struct Pos {
+pub struct Pos {
     id: usize,
     x: i32,
     y: i32,
 }
 
 impl Pos {
-    fn from_str(id: usize, coord_str: &str) -> Pos {
+    fn from_str(id: usize, coord_str: &str) -> Result<Pos> {
         let coords: Vec<_> = coord_str.split(',').map(|field| field.trim()).collect();
-        Pos {
-            id,
-            x: coords[0].parse::<i32>().unwrap(),
-            y: coords[1].parse::<i32>().unwrap(),
+        if coords.len() != 2 {
+            return Err(Error::parse(format!("expected coordinates like '1, 6', got '{}'",
+                                            coord_str)));
         }
-    }
-}
-
 
-fn abs_diff(a: i32, b: i32) -> u32 {
-    if a < b {
-        return (b-a) as u32;
-    } else {
-        return (a-b) as u32;
+        Ok(Pos {
+            id,
+            x: utils::parse_num(coords[0])?,
+            y: utils::parse_num(coords[1])?,
+        })
     }
 }
 
 
 /// Manhattan distance
 fn dist(pos1: &Pos, pos2: &Pos) -> u32 {
-    abs_diff(pos1.x,pos2.x) + abs_diff(pos1.y, pos2.y)
+    pos1.x.abs_diff(pos2.x) + pos1.y.abs_diff(pos2.y)
 }
 
 
 /// Return distances to from Pos to all Location coordinates, as a vector like
 /// [(locationPos1, dist(pos, locationPos1), (locationPos2, dist(pos, locationPos2), ..]
-fn get_coord_distance_map(pos: &Pos, coords: &Vec<Pos>) -> Vec<(Pos, u32)> {
+fn get_coord_distance_map(pos: &Pos, coords: &[Pos]) -> Vec<(Pos, u32)> {
     let dists_to_coords = coords.iter()
-        .map(|ipos| (ipos.clone(), dist(ipos, pos)))
+        .map(|ipos| (*ipos, dist(ipos, pos)))
         .collect::<Vec<_>>();
 
     dists_to_coords
@@ -51,7 +53,7 @@ fn get_coord_distance_map(pos: &Pos, coords: &Vec<Pos>) -> Vec<(Pos, u32)> {
 // Problem 1
 // ==================================================
 
-fn get_closest_pos(pos: &Pos, coords: &Vec<Pos>) -> Option<usize> {
+fn get_closest_pos(pos: &Pos, coords: &[Pos]) -> Option<usize> {
     let dists_to_coords = get_coord_distance_map(pos, coords);
 
     let closest_pos = dists_to_coords
@@ -79,11 +81,10 @@ fn get_closest_pos(pos: &Pos, coords: &Vec<Pos>) -> Option<usize> {
 /// closest location (or None if multiple exist) and give this location
 /// a +1 count. If the location borders on the edge of the region we check
 /// it extends to infinity and gets negative counts (hack to ignore this area).
-fn get_largest_area(coords: &Vec<Pos>) -> i32 {
+fn get_largest_area(coords: &[Pos]) -> Result<i32> {
     let max_x = coords.iter().map(|pos| pos.x).max().unwrap()+1;
     let max_y = coords.iter().map(|pos| pos.y).max().unwrap()+1;
 
-    println!("Checking areas of size {} x {}", max_x, max_y);
     let mut area_sizes = HashMap::new();
 
     for x in 0..max_x {
@@ -102,8 +103,11 @@ fn get_largest_area(coords: &Vec<Pos>) -> i32 {
         }
     }
 
-    println!("{:?}", area_sizes);
-    *area_sizes.values().max().unwrap()
+    // with the penalty, only finite areas have a positive size
+    area_sizes.values().cloned()
+        .filter(|&size| size > 0)
+        .max()
+        .ok_or_else(|| Error::no_solution("all areas are infinite"))
 }
 
 // Problem 2
@@ -111,14 +115,12 @@ fn get_largest_area(coords: &Vec<Pos>) -> i32 {
 
 /// The safe area is supposed to consist only of those coordinates
 /// which have a sum of manhattan distances to all locations which
-/// is smaller than 10000. We simply count the occurrences of
+/// is smaller than `max_dist_sum`. We simply count the occurrences of
 /// coordinates which satisfy that, which is enought to find the safe area.
-fn get_safe_area(coords: &Vec<Pos>) -> u32 {
+fn get_safe_area(coords: &[Pos], max_dist_sum: u32) -> u32 {
     let max_x = coords.iter().map(|pos| pos.x).max().unwrap()+1;
     let max_y = coords.iter().map(|pos| pos.y).max().unwrap()+1;
 
-    println!("Checking areas of size {} x {}", max_x, max_y);
-
     let mut compact_area_size = 0;
     for x in 0..max_x {
         for y in 0..max_y {
@@ -129,7 +131,7 @@ fn get_safe_area(coords: &Vec<Pos>) -> u32 {
                 .map(|(_, d)| d)
                 .sum::<u32>();
 
-            if current_pos_dist_sum < 10000 {
+            if current_pos_dist_sum < max_dist_sum {
                 compact_area_size += 1;
             }
 
@@ -143,30 +145,45 @@ fn get_safe_area(coords: &Vec<Pos>) -> u32 {
 // Interface
 // ==================================================
 
-pub fn solution1() -> () {
-    let coordinates = utils::file_to_string(INPUT);
-    let coordinates: Vec<_> = coordinates.lines().enumerate()
-        .map(|(num, line)| Pos::from_str(num, line)).collect();
+pub struct Day6;
 
-    let max_area = get_largest_area(&coordinates);
-    println!("Largest area: {}", max_area);
+impl Day for Day6 {
+    type Input = Vec<Pos>;
+    type Output1 = i32;
+    type Output2 = u32;
 
-}
+    fn parse(input: &str) -> Result<Vec<Pos>> {
+        Day6::parse_reader(input.as_bytes())
+    }
 
+    fn parse_reader<R: BufRead>(reader: R) -> Result<Vec<Pos>> {
+        let mut num = 0;
+        let coordinates = utils::parse_reader_lines(reader, |line| {
+            num += 1;
+            Pos::from_str(num - 1, line)
+        })?;
 
-pub fn solution2() -> () {
-    let coordinates = utils::file_to_string(INPUT);
-    let coordinates: Vec<_> = coordinates.lines().enumerate()
-        .map(|(num, line)| Pos::from_str(num, line)).collect();
+        if coordinates.is_empty() {
+            return Err(Error::parse("the input has no coordinates"));
+        }
+
+        Ok(coordinates)
+    }
+
+    fn part1(coordinates: &Vec<Pos>) -> Result<i32> {
+        get_largest_area(coordinates)
+    }
 
-    let min_all_dists = get_safe_area(&coordinates);
-    println!("The safe area has size {}", min_all_dists);
+    fn part2(coordinates: &Vec<Pos>) -> Result<u32> {
+        Ok(get_safe_area(coordinates, SAFE_DISTANCE))
+    }
 }
 
 
-pub fn solve_day() {
-    solution1();
-    solution2();
+#[cfg(test)]
+fn solution1(input: &str) -> Result<i32> {
+    let coordinates = Day6::parse(input)?;
+    Day6::part1(&coordinates)
 }
 
 
@@ -176,16 +193,23 @@ pub fn solve_day() {
 mod test {
     use super::*;
 
+    static COORDINATES: &str = "1, 1\n1, 6\n8, 3\n3, 4\n5, 5\n8, 9\n";
+
     #[test]
     fn test_samples1() {
-        let coordinates = ["1, 1", "1, 6", "8, 3", "3, 4", "5, 5", "8, 9"];
-        let coordinates: Vec<_> = coordinates.iter().enumerate()
-            .map(|(num, line)| Pos::from_str(num, line)).collect();
-        assert_eq!(get_largest_area(&coordinates), 17);
+        assert_eq!(solution1(COORDINATES).unwrap(), 17);
     }
 
     #[test]
     fn test_samples2() {
+        // the sample uses a total distance below 32 instead of 10000
+        let coordinates = Day6::parse(COORDINATES).unwrap();
+        assert_eq!(get_safe_area(&coordinates, 32), 16);
+    }
 
+    #[test]
+    fn test_bad_coordinates() {
+        let err = Day6::parse("1, 1\n1 6").err().unwrap();
+        assert_eq!(err.to_string(), "line 2: expected coordinates like '1, 6', got '1 6'");
     }
 }
@@ -1,19 +1,23 @@
 use std::collections::HashMap;
 use std::collections::HashSet;
-use binary_heap_plus::{BinaryHeap, MinComparator};
-use std::cell::RefCell;
-use std::cmp;
+use std::io::BufRead;
+use binary_heap_plus::BinaryHeap;
 
+use solutions::day::Day;
+use solutions::error::{Error, Result};
 use solutions::utils;
 
-static INPUT: &str = "data/input7";
+pub static INPUT: &str = "input7";
+
+/// Number of workers (including us) and the base cost of every step in problem 2.
+const WORKERS: usize = 5;
+const BASE_STEP_COST: u32 = 60;
 
 
 #[derive(Debug)]
-struct StepNode {
+pub struct StepNode {
     requirements: HashSet<String>,
     dependency: Vec<String>,
-    start_time: u32,
 }
 
 impl StepNode {
@@ -21,146 +25,171 @@ impl StepNode {
         StepNode {
             requirements: HashSet::new(),
             dependency: Vec::new(),
-            start_time: 0,
         }
     }
 }
 
 /// since we used String (unnecessarily) for the step IDs, we convert
 /// them to char here and get the duration for the first char
-fn get_duration_for_id(id: &str) -> u32 {
+fn get_duration_for_id(id: &str, base_cost: u32) -> u32 {
     let str_bytes = id.bytes().collect::<Vec<_>>();
 
-    ((str_bytes[0] - 64) as u32)
-//    60 + ((str_bytes[0] - 64) as u32)
+    base_cost + ((str_bytes[0] - 64) as u32)
 }
 
-fn create_step_graph(instructions: Vec<&str>) -> HashMap<String, RefCell<StepNode>> {
-    let mut step_graph = HashMap::new();
-    for instruction in instructions {
-        let fields = instruction.split_whitespace().collect::<Vec<_>>();
-        let requirement = fields[1].to_owned();
-        let dependency = fields[7].to_owned();
-
-        {
-            let required_node = step_graph
-                .entry(requirement.clone())
-                .or_insert(RefCell::new(StepNode::new()));
+/// Step IDs are single uppercase letters, which the durations rely on.
+fn parse_step_id(field: &str) -> Result<String> {
+    if field.len() == 1 && field.bytes().all(|b| b.is_ascii_uppercase()) {
+        Ok(field.to_owned())
+    } else {
+        Err(Error::parse(format!("invalid step '{}', steps are single uppercase letters", field)))
+    }
+}
 
-            required_node.borrow_mut().dependency.push(dependency.clone());
-        }
+/// Parse "Step C must be finished before step A can begin."
+/// into the required step "C" and the dependent step "A".
+fn parse_instruction(instruction: &str) -> Result<(String, String)> {
+    let fields = instruction.split_whitespace().collect::<Vec<_>>();
+    if fields.len() != 10 || fields[0] != "Step" || fields[6] != "step" {
+        return Err(Error::parse(format!("expected an instruction like 'Step C must be \
+                                         finished before step A can begin.', got '{}'",
+                                        instruction.trim())));
+    }
 
-        {
-            let dependent_node = step_graph
-                .entry(dependency.clone())
-                .or_insert(RefCell::new(StepNode::new()));
+    Ok((parse_step_id(fields[1])?, parse_step_id(fields[7])?))
+}
 
-            dependent_node.borrow_mut().requirements.insert(requirement.clone());
+/// Build the graph while the instructions are read.
+fn create_step_graph<R: BufRead>(reader: R) -> Result<HashMap<String, StepNode>> {
+    let mut step_graph = HashMap::new();
+    for (num, line) in utils::read_lines(reader).enumerate() {
+        let (requirement, dependency) = line.and_then(|line| parse_instruction(&line))
+            .map_err(|err| err.at_line(num + 1))?;
+
+        // repeated instructions must not count the requirement twice
+        let is_new = step_graph
+            .entry(dependency.clone())
+            .or_insert_with(StepNode::new)
+            .requirements.insert(requirement.clone());
+
+        let required = step_graph.entry(requirement).or_insert_with(StepNode::new);
+        if is_new {
+            required.dependency.push(dependency);
         }
     }
 
-    step_graph
+    Ok(step_graph)
 }
 
 
-// Problem 1
+// Problem 1 and 2
 // ==================================================
-/// the predecessor node has accumulated some total processing time,
-/// which potentially increases the time needed to finish the node
-/// (this is similar to the classical shortest path step `dist + t < current_dist`)
-fn update_node_processing_time(predecessor: &StepNode, node: &mut StepNode, predecessor_time: u32) {
-    if predecessor.start_time + predecessor_time > node.start_time {
-        node.start_time = predecessor.start_time + predecessor_time;
-    }
-}
-
-/// Do a topological sort over the step graph, breaking ties by lexicographic order.
-/// We do this as follows:
-///     1. Find all sinks (=no requirements), take the smallest, say S, by lexicographic order
-///     2. Remove S from the requirements of the steps it points to and remove S from the graph
-///     3. If the graph is not empty, go back to step 1. (Old sinks are still sinks but
-///        satisfying S might have created more sinks which are smaller.)
+/// Execute all steps with a number of workers, breaking ties by lexicographic order.
+/// This is a topological sort over the step graph that also keeps track of time:
+///     1. Find all sinks (=no open requirements) and hand the smallest ones (by lexicographic
+///        order) to idle workers, as long as there are idle workers.
+///     2. Advance the time to the next moment a worker finishes its step, say S. (With
+///        several steps finishing at once, they complete in lexicographic order.)
+///     3. Remove S from the requirements of the steps it points to. Satisfying S might
+///        create new sinks, so go back to step 1 until all steps are done.
 ///     The sink set is maintained in a priority queue (by lexicographic order).
-fn find_instruction_order(step_graph: HashMap<String, RefCell<StepNode>>,
-                          num_workers: usize) -> (u32, String) {
+/// Returns the total time and the order in which the steps completed.
+fn find_instruction_order(step_graph: &HashMap<String, StepNode>,
+                          num_workers: usize,
+                          base_cost: u32) -> Result<(u32, String)> {
+    let mut open_requirements = step_graph
+        .iter()
+        .map(|(id, node)| (id.as_str(), node.requirements.len()))
+        .collect::<HashMap<_, _>>();
+
     // find initial set of sinks by iterating through all nodes. we push values
     // manually because collect::<..> doesn't seem to work for the min heap variant.
     let mut sink_set = BinaryHeap::new_min();
-    for sink_node_id in step_graph
-        .iter()
-        .filter(|&(_, node)| node.borrow().requirements.is_empty())
-        .map(|(id, _)| id.clone()) {
+    for (&sink_node_id, _) in open_requirements.iter().filter(|&(_, &count)| count == 0) {
         sink_set.push(sink_node_id);
     }
 
-    while !sink_set.is_empty() {
-        let sink_node_id = sink_set.pop().unwrap();
-
-        let removed_sink_node = step_graph.get(&sink_node_id).unwrap();
-
-        // remove this node as a requirement from the nodes that depend on it
-        for dependent_node_id in &removed_sink_node.borrow().dependency {
-            let dependent_node = step_graph.get(dependent_node_id).unwrap();
-            dependent_node.borrow_mut().requirements.remove(&sink_node_id);
+    // steps in progress as (finish time, step id)
+    let mut in_progress: Vec<(u32, &str)> = Vec::with_capacity(num_workers);
+    let mut completion_order = String::new();
+    let mut time = 0;
+
+    loop {
+        while in_progress.len() < num_workers.max(1) {
+            match sink_set.pop() {
+                Some(step_id) =>
+                    in_progress.push((time + get_duration_for_id(step_id, base_cost), step_id)),
+                None => break,
+            }
+        }
 
-            // check if new sinks are formed when removing this node
-            // if yes, add them to our priority queue, with updated times
-            if dependent_node.borrow().requirements.is_empty() {
-                update_node_processing_time(&removed_sink_node.borrow(),
-                                            &mut dependent_node.borrow_mut(),
-                                            get_duration_for_id(&sink_node_id));
+        // nothing left to work on: either we are done or the rest is blocked
+        if in_progress.is_empty() {
+            break;
+        }
 
-                sink_set.push(dependent_node_id.clone());
+        // steps that finish at the same time complete in lexicographic order
+        in_progress.sort_by(|a, b| b.cmp(a));
+        time = in_progress.last().unwrap().0;
+
+        while in_progress.last().is_some_and(|&(finish_time, _)| finish_time == time) {
+            let (_, step_id) = in_progress.pop().unwrap();
+            completion_order.push_str(step_id);
+
+            // remove this node as a requirement from the nodes that depend on it
+            // and check if new sinks are formed when removing this node
+            for dependent_node_id in &step_graph[step_id].dependency {
+                let open = open_requirements.get_mut(dependent_node_id.as_str()).unwrap();
+                *open -= 1;
+                if *open == 0 {
+                    sink_set.push(dependent_node_id.as_str());
+                }
             }
         }
     }
 
-    let mut sorted_graph_nodes = step_graph
-        .iter()
-        .map(|(key, node)| (key, node.borrow().start_time + get_duration_for_id(key)))
-        .collect::<Vec<_>>();
-
-    sorted_graph_nodes.sort_by_key(|(_, duration)| *duration);
-    println!("{:?}", sorted_graph_nodes);
-
+    if completion_order.len() < step_graph.len() {
+        let mut blocked = open_requirements
+            .iter()
+            .filter(|&(_, &count)| count > 0)
+            .map(|(&id, _)| id)
+            .collect::<Vec<_>>();
+        blocked.sort();
 
-    let duration = sorted_graph_nodes.last().unwrap().1;
-    let mut completion_order = String::new();
-    for (key, _) in sorted_graph_nodes {
-        completion_order.push_str(key);
+        return Err(Error::no_solution(format!("steps {} have circular requirements",
+                                              blocked.join(", "))));
     }
 
-
-    (duration, completion_order)
+    Ok((time, completion_order))
 }
 
-// Problem 2
-// ==================================================
-
 // Interface
 // ==================================================
 
-pub fn solution1() -> () {
-    let instructions = utils::file_to_string(INPUT);
-    let instructions = instructions.lines().collect::<Vec<_>>();
-    let step_graph = create_step_graph(instructions);
-    let (processing_time, instruction_order) = find_instruction_order(step_graph, 1);
-    println!("Instructions should be executed as '{}' in {}s", instruction_order, processing_time);
-}
+pub struct Day7;
 
+impl Day for Day7 {
+    type Input = HashMap<String, StepNode>;
+    type Output1 = String;
+    type Output2 = u32;
 
-pub fn solution2() -> () {
-    let instructions = utils::file_to_string(INPUT);
-    let instructions = instructions.lines().collect::<Vec<_>>();
-    let step_graph = create_step_graph(instructions);
-    let (processing_time, instruction_order) = find_instruction_order(step_graph, 2);
-    println!("Instructions should be executed as '{}' in {}s", instruction_order, processing_time);
-}
+    fn parse(input: &str) -> Result<HashMap<String, StepNode>> {
+        create_step_graph(input.as_bytes())
+    }
+
+    fn parse_reader<R: BufRead>(reader: R) -> Result<HashMap<String, StepNode>> {
+        create_step_graph(reader)
+    }
 
+    fn part1(step_graph: &HashMap<String, StepNode>) -> Result<String> {
+        let (_, instruction_order) = find_instruction_order(step_graph, 1, BASE_STEP_COST)?;
+        Ok(instruction_order)
+    }
 
-pub fn solve_day() {
-    solution1();
-    solution2();
+    fn part2(step_graph: &HashMap<String, StepNode>) -> Result<u32> {
+        let (processing_time, _) = find_instruction_order(step_graph, WORKERS, BASE_STEP_COST)?;
+        Ok(processing_time)
+    }
 }
 
 
@@ -181,22 +210,51 @@ mod test {
 
     #[test]
     fn test_samples1() {
-        let instructions = TEST_INSTRUCTIONS.lines().collect::<Vec<_>>();
-        let step_graph = create_step_graph(instructions);
+        let step_graph = create_step_graph(TEST_INSTRUCTIONS.as_bytes()).unwrap();
         let (_, instruction_order) =
-            find_instruction_order(step_graph, 1);
+            find_instruction_order(&step_graph, 1, 0).unwrap();
 
         assert_eq!(instruction_order, "CABDFE");
     }
 
-//    #[test]
-//    fn test_samples2() {
-//        let instructions = TEST_INSTRUCTIONS.lines().collect::<Vec<_>>();
-//        let step_graph = create_step_graph(instructions);
-//        let (instruction_time, instruction_order2) =
-//            find_instruction_order(step_graph, 2);
-//
-//        assert_eq!(instruction_order2, "CABFDE");
-//        assert_eq!(instruction_time, 375);
-//    }
+    #[test]
+    fn test_samples2() {
+        let step_graph = create_step_graph(TEST_INSTRUCTIONS.as_bytes()).unwrap();
+        let (instruction_time, instruction_order2) =
+            find_instruction_order(&step_graph, 2, 0).unwrap();
+
+        assert_eq!(instruction_order2, "CABFDE");
+        assert_eq!(instruction_time, 15);
+    }
+
+    #[test]
+    fn test_single_worker_time() {
+        // with one worker, the total time is the sum of all step durations
+        let step_graph = create_step_graph(TEST_INSTRUCTIONS.as_bytes()).unwrap();
+        let (instruction_time, _) = find_instruction_order(&step_graph, 1, 60).unwrap();
+
+        assert_eq!(instruction_time, 6 * 60 + 1 + 2 + 3 + 4 + 5 + 6);
+    }
+
+    #[test]
+    fn test_circular_steps() {
+        let step_graph = create_step_graph(
+            "Step A must be finished before step B can begin.
+             Step B must be finished before step C can begin.
+             Step C must be finished before step B can begin.".as_bytes()).unwrap();
+
+        let err = find_instruction_order(&step_graph, 2, 0).unwrap_err();
+        assert_eq!(err.to_string(), "no solution: steps B, C have circular requirements");
+    }
+
+    #[test]
+    fn test_duplicate_instructions() {
+        let step_graph = create_step_graph(
+            "Step A must be finished before step B can begin.
+             Step A must be finished before step B can begin.
+             Step B must be finished before step C can begin.".as_bytes()).unwrap();
+
+        assert_eq!(step_graph["A"].dependency, vec!["B"]);
+        assert_eq!(find_instruction_order(&step_graph, 1, 0).unwrap(), (6, String::from("ABC")));
+    }
 }
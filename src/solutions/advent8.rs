@@ -1,15 +1,43 @@
+use solutions::day::Day;
+use solutions::error::{Error, Result};
 use solutions::utils;
 
-static INPUT: &str = "data/input8";
+pub static INPUT: &str = "input8";
+
+fn truncated(tree_spec: &[usize]) -> Error {
+    Error::parse(format!("the tree ends early after {} numbers", tree_spec.len()))
+}
+
+/// The end of a node's metadata, which starts at `child_offset`. The metadata
+/// count comes straight from the input, so it may not even fit in a `usize`.
+fn metadata_end(tree_spec: &[usize], child_offset: usize) -> Result<usize> {
+    child_offset.checked_add(tree_spec[1])
+        .filter(|&end| end <= tree_spec.len())
+        .ok_or_else(|| truncated(tree_spec))
+}
+
+/// Walk the tree without summing anything and return its length, so the
+/// metadata sums below can index the specification without bounds checks.
+fn check_tree(tree_spec: &[usize]) -> Result<usize> {
+    if tree_spec.len() < 2 {
+        return Err(truncated(tree_spec));
+    }
+
+    let mut child_offset = 2;
+    for _ in 0..tree_spec[0] {
+        child_offset += check_tree(&tree_spec[child_offset..]).map_err(|_| truncated(tree_spec))?;
+    }
+
+    metadata_end(tree_spec, child_offset)
+}
 
 // Problem 1
 // ==================================================
 
 /// The return type is(subtree length, subtree metadata sum).
 /// The subtree length is used to pass recursion information upwards.
-fn sum_metadata(tree_spec: &[usize]) -> (usize, usize) {
+fn sum_metadata(tree_spec: &[usize]) -> Result<(usize, usize)> {
     let children = tree_spec[0];
-    let metadata = tree_spec[1];
 
     let mut child_offset = 2;
     let mut metadata_sum = 0;
@@ -20,26 +48,25 @@ fn sum_metadata(tree_spec: &[usize]) -> (usize, usize) {
     // will end the recursion. (this recursion could only
     // go on indefinitely if the tree input was wrong)
     for _ in 0..children {
-        let (child_len, child_sum) = sum_metadata(&tree_spec[child_offset..]);
+        let (child_len, child_sum) = sum_metadata(&tree_spec[child_offset..])?;
         child_offset += child_len;
         metadata_sum += child_sum;
     }
 
-    let own_len = child_offset + metadata;
+    let own_len = metadata_end(tree_spec, child_offset)?;
 
     // after the children, sum own metadata
     // (this will start at 2 if no children)
-    metadata_sum += tree_spec[child_offset..(child_offset + metadata)].iter().sum::<usize>();
+    metadata_sum += tree_spec[child_offset..own_len].iter().sum::<usize>();
 
-    (own_len, metadata_sum)
+    Ok((own_len, metadata_sum))
 }
 
 // Problem 2
 // ==================================================
 
-fn indexed_sum_metadata(tree_spec: &[usize]) -> (usize, usize) {
+fn indexed_sum_metadata(tree_spec: &[usize]) -> Result<(usize, usize)> {
     let children = tree_spec[0];
-    let metadata = tree_spec[1];
 
     let mut child_offset = 2;
     let mut child_metadata_sums = Vec::new();
@@ -50,63 +77,65 @@ fn indexed_sum_metadata(tree_spec: &[usize]) -> (usize, usize) {
     // will end the recursion. (this recursion could only
     // go on indefinitely if the tree input was wrong)
     for _ in 0..children {
-        let (child_len, child_sum) = indexed_sum_metadata(&tree_spec[child_offset..]);
+        let (child_len, child_sum) = indexed_sum_metadata(&tree_spec[child_offset..])?;
         child_offset += child_len;
         child_metadata_sums.push(child_sum);
     }
 
-    let own_len = child_offset + metadata;
+    let own_len = metadata_end(tree_spec, child_offset)?;
 
     let mut metadata_sum = 0;
     // if we have no children, just sum metadata normally
     if child_metadata_sums.is_empty() {
-        metadata_sum += tree_spec[child_offset..(child_offset + metadata)].iter().sum::<usize>();
+        metadata_sum += tree_spec[child_offset..own_len].iter().sum::<usize>();
     } else {
         // given a child metadata sum vector, we now
         // use our own metadata vector to index it
-        for i in child_offset..(child_offset + metadata) {
-            let metadata_index = tree_spec[i];
+        for &metadata_index in &tree_spec[child_offset..own_len] {
             if 0 < metadata_index && metadata_index <= child_metadata_sums.len() {
                 metadata_sum += child_metadata_sums[metadata_index - 1];
             }
         }
     }
 
-    (own_len, metadata_sum)
+    Ok((own_len, metadata_sum))
 }
 
 // Interface
 // ==================================================
 
-pub fn solution1() -> () {
-    let tree_spec = utils::file_to_string(INPUT);
-    let tree_spec = tree_spec
-        .trim_right()
-        .split_whitespace()
-        .map(|num| num.parse::<usize>().unwrap())
-        .collect::<Vec<_>>();
+pub struct Day8;
 
-    let (tree_len, tree_metadata_sum) = sum_metadata(&tree_spec[..]);
-    println!("Checksum of tree (Len {}) is {}", tree_len, tree_metadata_sum);
-}
+impl Day for Day8 {
+    type Input = Vec<usize>;
+    type Output1 = usize;
+    type Output2 = usize;
 
+    fn parse(input: &str) -> Result<Vec<usize>> {
+        let tree_spec = utils::parse_lines(input, |line| {
+            line.split_whitespace()
+                .map(utils::parse_num)
+                .collect::<Result<Vec<usize>>>()
+        })?.concat();
 
-pub fn solution2() -> () {
-    let tree_spec = utils::file_to_string(INPUT);
-    let tree_spec = tree_spec
-        .trim_right()
-        .split_whitespace()
-        .map(|num| num.parse::<usize>().unwrap())
-        .collect::<Vec<_>>();
+        let tree_len = check_tree(&tree_spec)?;
+        if tree_len < tree_spec.len() {
+            return Err(Error::parse(format!("{} numbers are left over after the tree",
+                                            tree_spec.len() - tree_len)));
+        }
 
-    let (_, tree_metadata_sum) = indexed_sum_metadata(&tree_spec[..]);
-    println!("Indexed checksum of the tree is {}", tree_metadata_sum);
-}
+        Ok(tree_spec)
+    }
 
+    fn part1(tree_spec: &Vec<usize>) -> Result<usize> {
+        let (_, tree_metadata_sum) = sum_metadata(&tree_spec[..])?;
+        Ok(tree_metadata_sum)
+    }
 
-pub fn solve_day() {
-    solution1();
-    solution2();
+    fn part2(tree_spec: &Vec<usize>) -> Result<usize> {
+        let (_, tree_metadata_sum) = indexed_sum_metadata(&tree_spec[..])?;
+        Ok(tree_metadata_sum)
+    }
 }
 
 
@@ -121,12 +150,11 @@ mod test {
     #[test]
     fn test_samples1() {
         let tree_spec = TREE_SPEC
-            .trim_right()
             .split_whitespace()
             .map(|num| num.parse::<usize>().unwrap())
             .collect::<Vec<_>>();
 
-        let (tree_len, tree_metadata_sum) = sum_metadata(&tree_spec[..]);
+        let (tree_len, tree_metadata_sum) = sum_metadata(&tree_spec[..]).unwrap();
         println!("{} - {}", tree_len, tree_metadata_sum);
         assert_eq!(tree_len, tree_spec.len());
         assert_eq!(tree_metadata_sum, 138);
@@ -135,14 +163,32 @@ mod test {
     #[test]
     fn test_samples2() {
         let tree_spec = TREE_SPEC
-            .trim_right()
             .split_whitespace()
             .map(|num| num.parse::<usize>().unwrap())
             .collect::<Vec<_>>();
 
-        let (tree_len, tree_metadata_sum) = indexed_sum_metadata(&tree_spec[..]);
+        let (tree_len, tree_metadata_sum) = indexed_sum_metadata(&tree_spec[..]).unwrap();
         println!("{} - {}", tree_len, tree_metadata_sum);
         assert_eq!(tree_len, tree_spec.len());
         assert_eq!(tree_metadata_sum, 66);
     }
+
+    #[test]
+    fn test_bad_tree() {
+        let err = Day8::parse("2 3 0 3 10 11 12 1 1 0 1 99 2 1 1").err().unwrap();
+        assert_eq!(err.to_string(), "invalid input: the tree ends early after 15 numbers");
+
+        let err = Day8::parse("0 1 5 7").err().unwrap();
+        assert_eq!(err.to_string(), "invalid input: 1 numbers are left over after the tree");
+
+        let err = Day8::parse("0 1\n-5").err().unwrap();
+        assert_eq!(err.to_string(),
+                   "line 2: invalid number '-5': invalid digit found in string");
+
+        // a metadata count that overflows the end of the node
+        let err = Day8::parse(&format!("1 {} 0 1 5", usize::MAX)).err().unwrap();
+        assert_eq!(err.to_string(), "invalid input: the tree ends early after 5 numbers");
+        assert!(sum_metadata(&[1, usize::MAX, 0, 1, 5]).is_err());
+        assert!(indexed_sum_metadata(&[1, usize::MAX, 0, 1, 5]).is_err());
+    }
 }
@@ -1,69 +1,182 @@
-use std::collections::VecDeque;
+use std::fmt;
+
+use solutions::circle::Circle;
+use solutions::day::Day;
+use solutions::error::{Error, Result};
+use solutions::utils;
+
+pub static INPUT: &str = "input9";
+
+/// Part 2 plays the same game with a hundred times as many marbles.
+const PART2_FACTOR: usize = 100;
+
+/// Parse "452 players; last marble is worth 71250 points"
+/// into the number of players and the value of the last marble.
+fn parse_game(input: &str) -> Result<(usize, usize)> {
+    let fields = input.split_whitespace().collect::<Vec<_>>();
+    if fields.len() != 8 || fields[1] != "players;" || fields[2..6] != ["last", "marble", "is", "worth"]
+        || fields[7] != "points" {
+        return Err(Error::parse(format!("expected a game like '9 players; last marble is worth \
+                                         25 points', got '{}'", input.trim())));
+    }
+
+    let players = utils::parse_num(fields[0])?;
+    if players == 0 {
+        return Err(Error::parse("the game needs at least one player"));
+    }
+
+    Ok((players, utils::parse_num(fields[6])?))
+}
 
 // Problem 1 and 2
 // ==================================================
 
-fn play_marble_game(players: usize, last_marble: usize) -> usize {
-    let mut player_scores = vec![0; players];
-    let mut circle = VecDeque::with_capacity(last_marble);
-    circle.push_back(0usize);
+/// The rules of a marble game, the puzzle's rules are the default.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarbleRules {
+    /// Marbles that are a multiple of this are scored instead of placed
+    /// (0 scores no marbles at all).
+    pub scoring_modulus: usize,
+    /// How many marbles counter-clockwise of the current one the marble
+    /// taken along with a scoring marble lies.
+    pub removal_offset: usize,
+    /// A placed marble goes between the marbles this many and one more
+    /// marble clockwise of the current one.
+    pub insertion_offset: usize,
+}
 
-    // we maintain the invariant that the active marble is always 
-    // the second-to-last element in the deque (so inserting the new
-    // marble is only a push_back)
+impl Default for MarbleRules {
+    fn default() -> MarbleRules {
+        MarbleRules { scoring_modulus: 23, removal_offset: 7, insertion_offset: 1 }
+    }
+}
 
-    for (marble, player) in (1..last_marble+1).zip((0..players).cycle()) {
-        if marble % 23 == 0 {
-            // add marble to player score
-            player_scores[player] += marble;
-
-            // rotate the circle so that the to-be-removed marble is third-to-last
-            // so after we remove it the second-to-last marble is the current marble
-            for _ in 0..6 {
-                let last_marble = circle.pop_back().unwrap();
-                circle.push_front(last_marble);
+/// The outcome of a marble game.
+#[derive(Debug)]
+pub struct GameResult {
+    /// The final score of every player, in turn order.
+    pub scores: Vec<usize>,
+    /// The circle before the first and after every turn, if it was traced.
+    pub trace: Option<Vec<String>>,
+}
+
+impl GameResult {
+    pub fn high_score(&self) -> usize {
+        self.scores.iter().cloned().max().unwrap_or(0)
+    }
+}
+
+/// The state of a game after a turn, handed to the `replay_marble_game`
+/// callback. Its `Display` shows the circle as in the puzzle text.
+pub struct Turn<'a> {
+    /// The 0-based player that took the turn, `None` before the first turn.
+    pub player: Option<usize>,
+    /// The marble played in the turn, 0 before the first turn.
+    pub marble: usize,
+    /// The circle, with the cursor on the current marble.
+    pub circle: &'a Circle<usize>,
+    /// The scores of all players so far.
+    pub scores: &'a [usize],
+}
+
+impl<'a> fmt::Display for Turn<'a> {
+    /// Starts at the lowest marble and shows the current marble in
+    /// parentheses, e.g. `[3]  0  2  1 (3)`, or `[-] (0)` before the first turn.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // the circle iterates from the current marble
+        let mut marbles = self.circle.iter().cloned().collect::<Vec<_>>();
+        let lowest = (0..marbles.len()).min_by_key(|&i| marbles[i]).unwrap_or(0);
+        marbles.rotate_left(lowest);
+        let current = (marbles.len() - lowest) % marbles.len().max(1);
+
+        let mut line = match self.player {
+            Some(player) => format!("[{}] ", player + 1),
+            None => String::from("[-] "),
+        };
+        for (i, marble) in marbles.iter().enumerate() {
+            if i == current {
+                // wide current marbles take the space in front of them
+                if *marble >= 10 {
+                    line.pop();
+                }
+                line.push_str(&format!("({})", marble));
+            } else {
+                line.push_str(&format!("{:>2} ", marble));
             }
+        }
+
+        f.write_str(&line)
+    }
+}
 
-            let n = circle.len();
-            // removing the marble causes 2 elements to shift, but that should be ok
-            let removed_marble = circle.remove(n-3).unwrap();
-            player_scores[player] += removed_marble;
+/// Play a game and call `on_turn` with the initial state and after every
+/// turn. Returns the final scores.
+pub fn replay_marble_game<F>(players: usize, last_marble: usize, rules: &MarbleRules, mut on_turn: F)
+    -> Vec<usize>
+where
+    F: FnMut(&Turn),
+{
+    let mut scores = vec![0; players];
+    let mut circle = Circle::with_capacity(last_marble + 1);
+    circle.insert(0usize);
+    on_turn(&Turn { player: None, marble: 0, circle: &circle, scores: &scores });
+
+    for (marble, player) in (1..last_marble+1).zip((0..players).cycle()) {
+        if marble.is_multiple_of(rules.scoring_modulus) {
+            // the player keeps this marble and the one removal_offset marbles
+            // counter-clockwise, the marble after that becomes current
+            circle.move_counter_clockwise(rules.removal_offset);
+            scores[player] += marble + circle.remove().unwrap_or(0);
         } else {
-            // append the new marble
-            circle.push_back(marble);
-            // ensure that the new marble (which is the new current marble)
-            // is second-to-last by rotating the first marble to last place
-            let first_marble = circle.pop_front().unwrap();
-            circle.push_back(first_marble);
+            circle.move_clockwise(rules.insertion_offset);
+            circle.insert(marble);
         }
+
+        on_turn(&Turn { player: Some(player), marble, circle: &circle, scores: &scores });
     }
 
-    *player_scores.iter().max().unwrap()
+    scores
+}
+
+fn play_marble_game(players: usize, last_marble: usize, rules: &MarbleRules, trace: bool) -> GameResult {
+    let mut lines = if trace { Some(Vec::new()) } else { None };
+    let scores = replay_marble_game(players, last_marble, rules, |turn| {
+        if let Some(ref mut lines) = lines {
+            lines.push(turn.to_string());
+        }
+    });
+
+    GameResult { scores, trace: lines }
 }
 
 // Interface
 // ==================================================
 
-pub fn solution1() -> () {
-    // don't need to read here since input is just two numbers
-    let players = 452;
-    let last_marble = 71250;
-    let score = play_marble_game(players, last_marble);
-    println!("Score for game with {} players and {} marbles is {}!", players, last_marble, score);
-}
+pub struct Day9;
+
+impl Day for Day9 {
+    /// (players, last marble)
+    type Input = (usize, usize);
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<(usize, usize)> {
+        parse_game(input).map_err(|err| err.at_line(1))
+    }
 
+    fn part1(&(players, last_marble): &(usize, usize)) -> Result<usize> {
+        Ok(play_marble_game(players, last_marble, &MarbleRules::default(), false).high_score())
+    }
 
-pub fn solution2() -> () {
-    let players = 452;
-    let last_marble = 71250*100;
-    let score = play_marble_game(players, last_marble);
-    println!("Score for game with {} players and {} marbles is {}!", players, last_marble, score);
+    fn part2(&(players, last_marble): &(usize, usize)) -> Result<usize> {
+        Ok(play_marble_game(players, last_marble * PART2_FACTOR, &MarbleRules::default(), false).high_score())
+    }
 }
 
 
-pub fn solve_day() {
-    solution1();
-    solution2();
+#[cfg(test)]
+fn solution1(input: &str) -> Result<usize> {
+    Day9::part1(&Day9::parse(input)?)
 }
 
 
@@ -79,8 +192,81 @@ mod test {
         let results = [8317, 146373, 2764, 54718, 37305];
 
         for ((players, marbles), score) in samples.iter().zip(results.iter()) {
-            let game_score = play_marble_game(*players, *marbles);
+            let game_score = play_marble_game(*players, *marbles, &MarbleRules::default(), false).high_score();
             assert_eq!(game_score, *score);
         }
     }
+
+    static EXAMPLE_TURNS: &str = "\
+[-] (0)
+[1]  0 (1)
+[2]  0 (2) 1 
+[3]  0  2  1 (3)
+[4]  0 (4) 2  1  3 
+[5]  0  4  2 (5) 1  3 
+[6]  0  4  2  5  1 (6) 3 
+[7]  0  4  2  5  1  6  3 (7)
+[8]  0 (8) 4  2  5  1  6  3  7 
+[9]  0  8  4 (9) 2  5  1  6  3  7 
+[1]  0  8  4  9  2(10) 5  1  6  3  7 
+[2]  0  8  4  9  2 10  5(11) 1  6  3  7 
+[3]  0  8  4  9  2 10  5 11  1(12) 6  3  7 
+[4]  0  8  4  9  2 10  5 11  1 12  6(13) 3  7 
+[5]  0  8  4  9  2 10  5 11  1 12  6 13  3(14) 7 
+[6]  0  8  4  9  2 10  5 11  1 12  6 13  3 14  7(15)
+[7]  0(16) 8  4  9  2 10  5 11  1 12  6 13  3 14  7 15 
+[8]  0 16  8(17) 4  9  2 10  5 11  1 12  6 13  3 14  7 15 
+[9]  0 16  8 17  4(18) 9  2 10  5 11  1 12  6 13  3 14  7 15 
+[1]  0 16  8 17  4 18  9(19) 2 10  5 11  1 12  6 13  3 14  7 15 
+[2]  0 16  8 17  4 18  9 19  2(20)10  5 11  1 12  6 13  3 14  7 15 
+[3]  0 16  8 17  4 18  9 19  2 20 10(21) 5 11  1 12  6 13  3 14  7 15 
+[4]  0 16  8 17  4 18  9 19  2 20 10 21  5(22)11  1 12  6 13  3 14  7 15 
+[5]  0 16  8 17  4 18(19) 2 20 10 21  5 22 11  1 12  6 13  3 14  7 15 
+[6]  0 16  8 17  4 18 19  2(24)20 10 21  5 22 11  1 12  6 13  3 14  7 15 
+[7]  0 16  8 17  4 18 19  2 24 20(25)10 21  5 22 11  1 12  6 13  3 14  7 15 
+";
+
+    #[test]
+    fn test_example_turns() {
+        let game = play_marble_game(9, 25, &MarbleRules::default(), true);
+        assert_eq!(game.scores, vec![0, 0, 0, 0, 32, 0, 0, 0, 0]);
+        assert_eq!(game.trace.unwrap(), EXAMPLE_TURNS.lines().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_replay() {
+        let mut turns = Vec::new();
+        replay_marble_game(9, 25, &MarbleRules::default(), |turn| {
+            turns.push((turn.player, turn.marble, turn.circle.current().cloned(), turn.scores[4]));
+        });
+
+        assert_eq!(turns.len(), 26);
+        assert_eq!(turns[0], (None, 0, Some(0), 0));
+        assert_eq!(turns[22], (Some(3), 22, Some(22), 0));
+        assert_eq!(turns[23], (Some(4), 23, Some(19), 32));
+    }
+
+    #[test]
+    fn test_rules() {
+        // without scoring the marbles are simply placed in the circle
+        let rules = MarbleRules { scoring_modulus: 0, removal_offset: 7, insertion_offset: 0 };
+        let game = play_marble_game(2, 3, &rules, true);
+        assert_eq!(game.high_score(), 0);
+        assert_eq!(game.trace.unwrap()[3], "[1]  0  1  2 (3)");
+
+        let rules = MarbleRules { scoring_modulus: 2, removal_offset: 1, insertion_offset: 0 };
+        // player 2 scores marble 2 with marble 0, then marble 4 with marble 1
+        assert_eq!(play_marble_game(2, 4, &rules, false).scores, vec![0, 7]);
+    }
+
+    #[test]
+    fn test_parse_game() {
+        assert_eq!(Day9::parse("9 players; last marble is worth 25 points\n").unwrap(), (9, 25));
+        assert_eq!(solution1("10 players; last marble is worth 1618 points").unwrap(), 8317);
+
+        let err = Day9::parse("9 players; last marble is worth lots of points").unwrap_err();
+        assert_eq!(err.to_string(), "line 1: expected a game like '9 players; last marble is worth \
+                                     25 points', got '9 players; last marble is worth lots of points'");
+        assert!(Day9::parse("0 players; last marble is worth 25 points").is_err());
+    }
 }
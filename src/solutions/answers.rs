@@ -0,0 +1,155 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use solutions::day::{Answers, Part};
+use solutions::error::{Error, Result};
+use solutions::utils;
+
+/// File with the accepted answers, next to the inputs.
+pub static ANSWERS_FILE: &str = "answers.toml";
+
+/// An answer that differs from the accepted one.
+#[derive(Debug, PartialEq)]
+pub struct Disagreement {
+    pub day: u32,
+    pub part: Part,
+    pub expected: String,
+    pub actual: String,
+}
+
+impl fmt::Display for Disagreement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "day {} part {}: expected '{}', got '{}'", self.day, self.part, self.expected, self.actual)
+    }
+}
+
+/// Compare the answers of a day with the accepted ones. Parts without an
+/// accepted answer or that were not run are not compared.
+pub fn compare(day: u32, accepted: &Answers, actual: &Answers) -> Vec<Disagreement> {
+    let parts = [(Part::One, &accepted.part1, &actual.part1), (Part::Two, &accepted.part2, &actual.part2)];
+    parts.iter()
+        .filter_map(|&(part, expected, actual)| match (expected, actual) {
+            (Some(expected), Some(actual)) if expected != actual => Some(Disagreement {
+                day,
+                part,
+                expected: expected.clone(),
+                actual: actual.clone(),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Parse a quoted string value, with `\"`, `\\` and `\n` escapes.
+fn parse_string(value: &str) -> Result<String> {
+    let inner = value.strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .ok_or_else(|| Error::parse(format!("expected a quoted string, got '{}'", value)))?;
+
+    let mut parsed = String::new();
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            parsed.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('"') => parsed.push('"'),
+            Some('\\') => parsed.push('\\'),
+            Some('n') => parsed.push('\n'),
+            other => return Err(Error::parse(
+                format!("invalid escape '\\{}'", other.map_or(String::new(), String::from)))),
+        }
+    }
+
+    Ok(parsed)
+}
+
+/// Parse the accepted answers per day from a small subset of TOML:
+///
+/// ```toml
+/// [day3]
+/// part1 = "97218"
+/// part2 = "717"
+/// ```
+///
+/// Comments (`#`) and blank lines are ignored, parts may be left out.
+pub fn parse_answers(text: &str) -> Result<BTreeMap<u32, Answers>> {
+    let mut days = BTreeMap::new();
+    let mut current = None;
+
+    for (num, line) in text.lines().enumerate() {
+        let at_line = |err: Error| err.at_line(num + 1);
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(table) = line.strip_prefix("[day").and_then(|line| line.strip_suffix(']')) {
+            let day: u32 = utils::parse_num(table).map_err(at_line)?;
+            if days.insert(day, Answers { part1: None, part2: None }).is_some() {
+                return Err(at_line(Error::parse(format!("day {} appears twice", day))));
+            }
+            current = Some(day);
+            continue;
+        }
+
+        let (key, value) = line.split_once('=')
+            .ok_or_else(|| at_line(Error::parse(format!("expected 'key = \"value\"', got '{}'", line))))?;
+        let answers = current.and_then(|day| days.get_mut(&day))
+            .ok_or_else(|| at_line(Error::parse("answer outside of a [dayN] table")))?;
+        let answer = Some(parse_string(value.trim()).map_err(at_line)?);
+
+        match key.trim() {
+            "part1" => answers.part1 = answer,
+            "part2" => answers.part2 = answer,
+            other => return Err(at_line(Error::parse(format!("unknown key '{}'", other)))),
+        }
+    }
+
+    Ok(days)
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_answers() {
+        let answers = parse_answers("# accepted answers\n\n[day3]\npart1 = \"97218\"\n\
+                                     part2 = \"717\"\n\n[day10]\npart1 = \"a \\\"b\\\"\\nc\"\n")
+            .unwrap();
+
+        assert_eq!(answers[&3], Answers { part1: Some("97218".to_owned()), part2: Some("717".to_owned()) });
+        assert_eq!(answers[&10], Answers { part1: Some("a \"b\"\nc".to_owned()), part2: None });
+        assert_eq!(answers.len(), 2);
+    }
+
+    #[test]
+    fn test_compare() {
+        let accepted = Answers { part1: Some("42".to_owned()), part2: None };
+        let actual = Answers { part1: Some("41".to_owned()), part2: Some("7".to_owned()) };
+
+        let disagreements = compare(3, &accepted, &actual);
+        assert_eq!(disagreements.len(), 1);
+        assert_eq!(disagreements[0].to_string(), "day 3 part 1: expected '42', got '41'");
+        assert!(compare(3, &accepted, &accepted).is_empty());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = parse_answers("part1 = \"1\"").unwrap_err();
+        assert_eq!(err.to_string(), "line 1: answer outside of a [dayN] table");
+
+        let err = parse_answers("[day1]\npart3 = \"1\"").unwrap_err();
+        assert_eq!(err.to_string(), "line 2: unknown key 'part3'");
+
+        let err = parse_answers("[day1]\npart1 = 1").unwrap_err();
+        assert_eq!(err.to_string(), "line 2: expected a quoted string, got '1'");
+
+        let err = parse_answers("[day1]\n[day1]").unwrap_err();
+        assert_eq!(err.to_string(), "line 2: day 1 appears twice");
+    }
+}
@@ -0,0 +1,195 @@
+/// A slot of the circle, linked to its neighbours by index.
+struct Node<T> {
+    /// `None` once the value is removed and the slot waits for reuse.
+    value: Option<T>,
+    /// Counter-clockwise neighbour.
+    prev: usize,
+    /// Clockwise neighbour.
+    next: usize,
+}
+
+/// Values arranged in a circle with a cursor on the current value, as in
+/// the marble game of 2018 day 9. Moving the cursor by one, inserting next
+/// to it and removing at it are all O(1): the circle is a doubly linked list
+/// kept in a `Vec`, and removed slots are reused by later insertions.
+pub struct Circle<T> {
+    nodes: Vec<Node<T>>,
+    free: Vec<usize>,
+    cursor: Option<usize>,
+    len: usize,
+}
+
+impl<T> Circle<T> {
+    pub fn new() -> Circle<T> {
+        Circle::with_capacity(0)
+    }
+
+    /// An empty circle with room for `capacity` values.
+    pub fn with_capacity(capacity: usize) -> Circle<T> {
+        Circle { nodes: Vec::with_capacity(capacity), free: Vec::new(), cursor: None, len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The value under the cursor, `None` if the circle is empty.
+    pub fn current(&self) -> Option<&T> {
+        self.cursor.and_then(|cursor| self.nodes[cursor].value.as_ref())
+    }
+
+    /// Move the cursor `steps` values clockwise.
+    pub fn move_clockwise(&mut self, steps: usize) {
+        if let Some(mut cursor) = self.cursor {
+            // going round the whole circle changes nothing
+            for _ in 0..steps % self.len {
+                cursor = self.nodes[cursor].next;
+            }
+            self.cursor = Some(cursor);
+        }
+    }
+
+    /// Move the cursor `steps` values counter-clockwise.
+    pub fn move_counter_clockwise(&mut self, steps: usize) {
+        if let Some(mut cursor) = self.cursor {
+            for _ in 0..steps % self.len {
+                cursor = self.nodes[cursor].prev;
+            }
+            self.cursor = Some(cursor);
+        }
+    }
+
+    /// Insert a value clockwise of the cursor and move the cursor onto it.
+    pub fn insert(&mut self, value: T) {
+        let (prev, next) = match self.cursor {
+            Some(cursor) => (cursor, self.nodes[cursor].next),
+            // the first value is its own neighbour on both sides
+            None => (self.next_slot(), self.next_slot()),
+        };
+
+        let node = Node { value: Some(value), prev, next };
+        let index = match self.free.pop() {
+            Some(index) => {
+                self.nodes[index] = node;
+                index
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        };
+
+        self.nodes[prev].next = index;
+        self.nodes[next].prev = index;
+        self.cursor = Some(index);
+        self.len += 1;
+    }
+
+    /// Remove the value under the cursor. The cursor moves on to the value
+    /// clockwise of it, `None` is returned if the circle is empty.
+    pub fn remove(&mut self) -> Option<T> {
+        let cursor = self.cursor?;
+        let (prev, next) = (self.nodes[cursor].prev, self.nodes[cursor].next);
+        self.nodes[prev].next = next;
+        self.nodes[next].prev = prev;
+
+        self.len -= 1;
+        self.cursor = if self.len == 0 { None } else { Some(next) };
+        self.free.push(cursor);
+        self.nodes[cursor].value.take()
+    }
+
+    /// The values in clockwise order, starting at the cursor.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { circle: self, node: self.cursor, remaining: self.len }
+    }
+
+    /// The slot the next insertion uses.
+    fn next_slot(&self) -> usize {
+        self.free.last().cloned().unwrap_or(self.nodes.len())
+    }
+}
+
+impl<T> Default for Circle<T> {
+    fn default() -> Circle<T> {
+        Circle::new()
+    }
+}
+
+/// Iterator over the values of a circle, see `Circle::iter`.
+pub struct Iter<'a, T: 'a> {
+    circle: &'a Circle<T>,
+    node: Option<usize>,
+    remaining: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let node = &self.circle.nodes[self.node?];
+        self.node = Some(node.next);
+        self.remaining -= 1;
+        node.value.as_ref()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn values(circle: &Circle<u32>) -> Vec<u32> {
+        circle.iter().cloned().collect()
+    }
+
+    #[test]
+    fn test_insert_and_move() {
+        let mut circle = Circle::new();
+        assert_eq!(circle.current(), None);
+
+        for value in 0..4 {
+            circle.insert(value);
+        }
+        assert_eq!(values(&circle), vec![3, 0, 1, 2]);
+
+        circle.move_clockwise(2);
+        assert_eq!(circle.current(), Some(&1));
+        circle.move_counter_clockwise(7);
+        assert_eq!(circle.current(), Some(&2));
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut circle = Circle::new();
+        for value in 0..4 {
+            circle.insert(value);
+        }
+
+        circle.move_counter_clockwise(1);
+        assert_eq!(circle.remove(), Some(2));
+        assert_eq!(values(&circle), vec![3, 0, 1]);
+
+        // removed slots are reused
+        circle.insert(4);
+        assert_eq!(values(&circle), vec![4, 0, 1, 3]);
+        assert_eq!(circle.nodes.len(), 4);
+
+        while circle.remove().is_some() {}
+        assert!(circle.is_empty());
+        circle.insert(5);
+        assert_eq!(values(&circle), vec![5]);
+    }
+}
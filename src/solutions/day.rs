@@ -0,0 +1,222 @@
+use std::fmt::{self, Display};
+use std::io::BufRead;
+use std::time::{Duration, Instant};
+
+use solutions::error::{Error, Result};
+
+/// The two parts of every puzzle.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+/// Common interface of all days: the puzzle input is parsed once
+/// and both parts compute a displayable answer from the parsed input.
+/// Malformed input and inputs without an answer are reported as errors.
+pub trait Day {
+    type Input;
+    type Output1: Display;
+    type Output2: Display;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+
+    /// Parse the input while it is read. Days that can parse line by line
+    /// override this, the default reads the whole input and calls `parse`.
+    fn parse_reader<R: BufRead>(mut reader: R) -> Result<Self::Input> {
+        let mut input = String::new();
+        reader.read_to_string(&mut input).map_err(|err| Error::Io(String::from("input"), err))?;
+        Self::parse(&input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Output1>;
+    fn part2(input: &Self::Input) -> Result<Self::Output2>;
+}
+
+/// Rendered answers of a day. Parts that were not run are `None`.
+#[derive(Debug, PartialEq)]
+pub struct Answers {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+/// The outcome of each part of a run. Parts that were not run are `None`,
+/// and a failing part does not hide the answer of the other one.
+#[derive(Debug)]
+pub struct Outcome {
+    pub part1: Option<Result<String>>,
+    pub part2: Option<Result<String>>,
+}
+
+impl Outcome {
+    /// The answers of the parts that were solved.
+    pub fn answers(&self) -> Answers {
+        Answers {
+            part1: self.part1.as_ref().and_then(|answer| answer.as_ref().ok()).cloned(),
+            part2: self.part2.as_ref().and_then(|answer| answer.as_ref().ok()).cloned(),
+        }
+    }
+
+    /// The parts that failed, with their errors.
+    pub fn errors(&self) -> Vec<(Part, &Error)> {
+        [(Part::One, &self.part1), (Part::Two, &self.part2)].iter()
+            .filter_map(|&(part, answer)| match answer {
+                Some(Err(err)) => Some((part, err)),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Wall-clock times of the steps of a run. Parts that were not run are `None`.
+#[derive(Debug, Clone, Copy)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part1: Option<Duration>,
+    pub part2: Option<Duration>,
+}
+
+/// Render a duration with a unit that fits its size, e.g. "12.34 ms".
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs_f64();
+    if secs >= 1.0 {
+        format!("{:.2} s", secs)
+    } else if secs >= 1e-3 {
+        format!("{:.2} ms", secs * 1e3)
+    } else {
+        format!("{:.2} µs", secs * 1e6)
+    }
+}
+
+impl Display for Timings {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "parse {}", format_duration(self.parse))?;
+        if let Some(time) = self.part1 {
+            write!(f, ", part 1 {}", format_duration(time))?;
+        }
+        if let Some(time) = self.part2 {
+            write!(f, ", part 2 {}", format_duration(time))?;
+        }
+        Ok(())
+    }
+}
+
+/// Run a step and measure how long it took.
+fn timed<T, F: FnOnce() -> T>(step: F) -> (T, Duration) {
+    let start = Instant::now();
+    let result = step();
+    (result, start.elapsed())
+}
+
+/// The type-erased `run` of a day.
+pub type Runner = fn(&mut dyn BufRead, Option<Part>) -> Result<(Outcome, Timings)>;
+
+/// Solve the selected part (or both parts) of a day on the input read from
+/// `input`. The parse time includes reading the input. Only a failure to
+/// read or parse the input fails the whole run.
+/// This erases the day-specific types, so all days can be run the same way.
+pub fn run<D: Day>(input: &mut dyn BufRead, part: Option<Part>) -> Result<(Outcome, Timings)> {
+    let (parsed, parse_time) = timed(|| D::parse_reader(input));
+    let parsed = parsed?;
+    let mut timings = Timings { parse: parse_time, part1: None, part2: None };
+
+    let part1 = match part {
+        Some(Part::Two) => None,
+        _ => {
+            let (answer, time) = timed(|| D::part1(&parsed));
+            timings.part1 = Some(time);
+            Some(answer.map(|answer| answer.to_string()))
+        }
+    };
+
+    let part2 = match part {
+        Some(Part::One) => None,
+        _ => {
+            let (answer, time) = timed(|| D::part2(&parsed));
+            timings.part2 = Some(time);
+            Some(answer.map(|answer| answer.to_string()))
+        }
+    };
+
+    Ok((Outcome { part1, part2 }, timings))
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use solutions::utils;
+
+    struct Sum;
+
+    impl Day for Sum {
+        type Input = Vec<i32>;
+        type Output1 = i32;
+        type Output2 = String;
+
+        fn parse(input: &str) -> Result<Vec<i32>> {
+            input.split_whitespace().map(utils::parse_num).collect()
+        }
+
+        fn part1(nums: &Vec<i32>) -> Result<i32> {
+            Ok(nums.iter().sum())
+        }
+
+        fn part2(nums: &Vec<i32>) -> Result<String> {
+            if nums.is_empty() {
+                return Err(Error::no_solution("there are no numbers"));
+            }
+            Ok(format!("{} numbers", nums.len()))
+        }
+    }
+
+    #[test]
+    fn test_run_parts() {
+        assert_eq!(run::<Sum>(&mut "1 2 3".as_bytes(), None).unwrap().0.answers(),
+                   Answers { part1: Some("6".to_owned()), part2: Some("3 numbers".to_owned()) });
+        assert_eq!(run::<Sum>(&mut "1 2 3".as_bytes(), Some(Part::One)).unwrap().0.answers(),
+                   Answers { part1: Some("6".to_owned()), part2: None });
+        assert_eq!(run::<Sum>(&mut "1 2 3".as_bytes(), Some(Part::Two)).unwrap().0.answers(),
+                   Answers { part1: None, part2: Some("3 numbers".to_owned()) });
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_millis(2500)), "2.50 s");
+        assert_eq!(format_duration(Duration::from_micros(12345)), "12.35 ms");
+        assert_eq!(format_duration(Duration::from_nanos(1500)), "1.50 µs");
+    }
+
+    #[test]
+    fn test_run_timings() {
+        let (_, timings) = run::<Sum>(&mut "1 2 3".as_bytes(), Some(Part::Two)).unwrap();
+        assert!(timings.part1.is_none());
+        assert!(timings.part2.is_some());
+    }
+
+    #[test]
+    fn test_run_part_error() {
+        // part 1 is still answered when part 2 fails
+        let (outcome, _) = run::<Sum>(&mut "".as_bytes(), None).unwrap();
+        assert_eq!(outcome.answers(), Answers { part1: Some("0".to_owned()), part2: None });
+
+        let errors = outcome.errors();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].0, Part::Two);
+        assert_eq!(errors[0].1.to_string(), "no solution: there are no numbers");
+    }
+
+    #[test]
+    fn test_run_parse_error() {
+        assert!(run::<Sum>(&mut "1 two 3".as_bytes(), None).is_err());
+    }
+}
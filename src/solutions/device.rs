@@ -0,0 +1,222 @@
+use std::fmt;
+use std::str::FromStr;
+
+use solutions::error::{Error, Result};
+use solutions::utils;
+
+/// The sixteen opcodes of the wrist device (days 16, 19 and 21).
+/// 'r' operands are registers, 'i' operands are immediate values.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Opcode {
+    Addr, Addi,
+    Mulr, Muli,
+    Banr, Bani,
+    Borr, Bori,
+    Setr, Seti,
+    Gtir, Gtri, Gtrr,
+    Eqir, Eqri, Eqrr,
+}
+
+pub static OPCODES: [Opcode; 16] = [
+    Opcode::Addr, Opcode::Addi, Opcode::Mulr, Opcode::Muli,
+    Opcode::Banr, Opcode::Bani, Opcode::Borr, Opcode::Bori,
+    Opcode::Setr, Opcode::Seti, Opcode::Gtir, Opcode::Gtri,
+    Opcode::Gtrr, Opcode::Eqir, Opcode::Eqri, Opcode::Eqrr,
+];
+
+impl Opcode {
+    pub fn name(self) -> &'static str {
+        match self {
+            Opcode::Addr => "addr", Opcode::Addi => "addi",
+            Opcode::Mulr => "mulr", Opcode::Muli => "muli",
+            Opcode::Banr => "banr", Opcode::Bani => "bani",
+            Opcode::Borr => "borr", Opcode::Bori => "bori",
+            Opcode::Setr => "setr", Opcode::Seti => "seti",
+            Opcode::Gtir => "gtir", Opcode::Gtri => "gtri", Opcode::Gtrr => "gtrr",
+            Opcode::Eqir => "eqir", Opcode::Eqri => "eqri", Opcode::Eqrr => "eqrr",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Opcode> {
+        OPCODES.iter().cloned().find(|op| op.name() == name)
+    }
+
+    /// Execute the opcode on the registers. Returns `None` (and leaves the
+    /// registers unchanged) if an operand refers to a register that doesn't exist.
+    pub fn execute(self, regs: &mut [usize], a: usize, b: usize, c: usize) -> Option<()> {
+        let reg = |i: usize| regs.get(i).cloned();
+
+        let value = match self {
+            Opcode::Addr => reg(a)? + reg(b)?,
+            Opcode::Addi => reg(a)? + b,
+            Opcode::Mulr => reg(a)? * reg(b)?,
+            Opcode::Muli => reg(a)? * b,
+            Opcode::Banr => reg(a)? & reg(b)?,
+            Opcode::Bani => reg(a)? & b,
+            Opcode::Borr => reg(a)? | reg(b)?,
+            Opcode::Bori => reg(a)? | b,
+            Opcode::Setr => reg(a)?,
+            Opcode::Seti => a,
+            Opcode::Gtir => (a > reg(b)?) as usize,
+            Opcode::Gtri => (reg(a)? > b) as usize,
+            Opcode::Gtrr => (reg(a)? > reg(b)?) as usize,
+            Opcode::Eqir => (a == reg(b)?) as usize,
+            Opcode::Eqri => (reg(a)? == b) as usize,
+            Opcode::Eqrr => (reg(a)? == reg(b)?) as usize,
+        };
+
+        *regs.get_mut(c)? = value;
+        Some(())
+    }
+}
+
+impl fmt::Display for Opcode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Instruction {
+    pub op: Opcode,
+    pub a: usize,
+    pub b: usize,
+    pub c: usize,
+}
+
+/// Number of registers of the device when the instruction pointer is bound.
+pub const REGISTERS: usize = 6;
+
+/// A program whose instruction pointer is bound to a register (days 19 and 21).
+#[derive(Debug)]
+pub struct Program {
+    pub ip_register: usize,
+    pub instructions: Vec<Instruction>,
+}
+
+/// Parse an instruction like "seti 5 0 1".
+fn parse_instruction(line: &str) -> Result<Instruction> {
+    let fields = line.split_whitespace().collect::<Vec<_>>();
+    if fields.len() != 4 {
+        return Err(Error::parse(format!("expected an instruction like 'seti 5 0 1', got '{}'",
+                                        line.trim())));
+    }
+
+    let op = Opcode::from_name(fields[0])
+        .ok_or_else(|| Error::parse(format!("unknown opcode '{}'", fields[0])))?;
+
+    Ok(Instruction {
+        op,
+        a: utils::parse_num(fields[1])?,
+        b: utils::parse_num(fields[2])?,
+        c: utils::parse_num(fields[3])?,
+    })
+}
+
+impl FromStr for Program {
+    type Err = Error;
+
+    /// Parse a program that starts with the "#ip N" declaration.
+    fn from_str(input: &str) -> Result<Program> {
+        let mut lines = input.lines();
+        let declaration = lines.next().unwrap_or("");
+        if !declaration.starts_with("#ip ") {
+            return Err(Error::parse(format!("expected '#ip N' declaration, got '{}'", declaration))
+                .at_line(1));
+        }
+
+        let ip_register: usize = utils::parse_num(&declaration[4..]).map_err(|err| err.at_line(1))?;
+        if ip_register >= REGISTERS {
+            return Err(Error::parse(format!("there is no register {}", ip_register)).at_line(1));
+        }
+
+        let rest = input.split_once('\n').map_or("", |(_, rest)| rest);
+        let instructions = utils::parse_lines(rest, parse_instruction)
+            .map_err(|err| match err {
+                Error::Parse { line: Some(line), message } =>
+                    Error::Parse { line: Some(line + 1), message },
+                other => other,
+            })?;
+
+        Ok(Program { ip_register, instructions })
+    }
+}
+
+/// Executes a program step by step.
+pub struct Cpu<'a> {
+    program: &'a Program,
+    pub regs: [usize; REGISTERS],
+    pub ip: usize,
+}
+
+impl<'a> Cpu<'a> {
+    pub fn new(program: &'a Program, reg0: usize) -> Cpu<'a> {
+        let mut regs = [0; REGISTERS];
+        regs[0] = reg0;
+        Cpu { program, regs, ip: 0 }
+    }
+
+    /// The instruction that executes next, `None` if the program halted.
+    pub fn next_instruction(&self) -> Option<&'a Instruction> {
+        self.program.instructions.get(self.ip)
+    }
+
+    /// Execute one instruction. Returns false if the program has halted.
+    pub fn step(&mut self) -> Result<bool> {
+        let instruction = match self.next_instruction() {
+            Some(instruction) => instruction,
+            None => return Ok(false),
+        };
+
+        self.regs[self.program.ip_register] = self.ip;
+        instruction.op.execute(&mut self.regs, instruction.a, instruction.b, instruction.c)
+            .ok_or_else(|| Error::no_solution(format!("instruction {} uses a missing register",
+                                                      self.ip)))?;
+        self.ip = self.regs[self.program.ip_register] + 1;
+
+        Ok(true)
+    }
+
+    /// Run until the program halts.
+    pub fn run(&mut self) -> Result<()> {
+        while self.step()? {}
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_opcode_names() {
+        for op in OPCODES.iter() {
+            assert_eq!(Opcode::from_name(op.name()), Some(*op));
+        }
+        assert_eq!(Opcode::from_name("divr"), None);
+    }
+
+    #[test]
+    fn test_execute() {
+        let mut regs = [3, 2, 1, 1];
+        Opcode::Mulr.execute(&mut regs, 2, 1, 2).unwrap();
+        assert_eq!(regs, [3, 2, 2, 1]);
+
+        assert_eq!(Opcode::Addr.execute(&mut regs, 7, 1, 2), None);
+        assert_eq!(regs, [3, 2, 2, 1]);
+    }
+
+    #[test]
+    fn test_program() {
+        let program = Program::from_str("#ip 0\nseti 5 0 1\nseti 6 0 2\naddi 0 1 0\n\
+                                         addr 1 2 3\nsetr 1 0 0\nseti 8 0 4\nseti 9 0 5")
+            .unwrap();
+        let mut cpu = Cpu::new(&program, 0);
+        cpu.run().unwrap();
+        assert_eq!(cpu.regs, [6, 5, 6, 0, 0, 9]);
+
+        let err = Program::from_str("#ip 0\nseti 5 0 1\ndivi 6 0 2").unwrap_err();
+        assert_eq!(err.to_string(), "line 3: unknown opcode 'divi'");
+    }
+}
@@ -0,0 +1,75 @@
+use std::error;
+use std::fmt;
+use std::io;
+use std::result;
+
+pub type Result<T> = result::Result<T, Error>;
+
+/// Everything that can go wrong while loading or solving a puzzle.
+#[derive(Debug)]
+pub enum Error {
+    /// The input could not be read from the given source.
+    Io(String, io::Error),
+    /// The input is malformed. `line` is the 1-based input line, if known.
+    Parse { line: Option<usize>, message: String },
+    /// The input is valid, but the puzzle has no answer for it.
+    NoSolution(String),
+}
+
+impl Error {
+    pub fn parse<S: Into<String>>(message: S) -> Error {
+        Error::Parse { line: None, message: message.into() }
+    }
+
+    pub fn no_solution<S: Into<String>>(message: S) -> Error {
+        Error::NoSolution(message.into())
+    }
+
+    /// Attach the (1-based) input line number to a parse error.
+    /// Other errors and errors that already know their line are unchanged.
+    pub fn at_line(self, line: usize) -> Error {
+        match self {
+            Error::Parse { line: None, message } => Error::Parse { line: Some(line), message },
+            other => other,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io(source, err) => write!(f, "could not read input from {}: {}", source, err),
+            Error::Parse { line: Some(line), message } => write!(f, "line {}: {}", line, message),
+            Error::Parse { line: None, message } => write!(f, "invalid input: {}", message),
+            Error::NoSolution(message) => write!(f, "no solution: {}", message),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Io(_, err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_at_line() {
+        let err = Error::parse("bad number").at_line(3);
+        assert_eq!(err.to_string(), "line 3: bad number");
+
+        // the innermost line number wins
+        let err = Error::parse("bad number").at_line(3).at_line(5);
+        assert_eq!(err.to_string(), "line 3: bad number");
+
+        let err = Error::no_solution("nothing repeats").at_line(3);
+        assert_eq!(err.to_string(), "no solution: nothing repeats");
+    }
+}
@@ -0,0 +1,115 @@
+/// An iterator that starts over from the beginning of the base iterator
+/// whenever it runs out, and counts the passes it made over it. It ends
+/// right away if the base iterator is empty.
+pub struct Looper<T> {
+    active_iter: T,
+    iter_base: T,
+    pass: usize,
+}
+
+/// Loop over `iter` endlessly, see `Looper`.
+pub fn looper<T: Iterator + Clone>(iter: T) -> Looper<T> {
+    Looper {
+        active_iter: iter.clone(),
+        iter_base: iter,
+        pass: 0,
+    }
+}
+
+impl<T> Looper<T> {
+    /// The 0-based pass the last item came from.
+    pub fn pass(&self) -> usize {
+        self.pass
+    }
+
+    /// Yield every item together with the 0-based pass it came from.
+    pub fn passes(self) -> Passes<T> {
+        Passes { looper: self }
+    }
+}
+
+impl<T> Iterator for Looper<T>
+where
+    T: Iterator + Clone
+{
+    type Item = <T as Iterator>::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(item) = self.active_iter.next() {
+            return Some(item);
+        }
+
+        // restart, unless there is nothing to restart with
+        self.active_iter = self.iter_base.clone();
+        let item = self.active_iter.next()?;
+        self.pass += 1;
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.iter_base.size_hint() {
+            empty @ (0, Some(0)) => empty,
+            // a base that may be empty may end the loop right away
+            (0, _) => (0, None),
+            _ => (usize::MAX, None),
+        }
+    }
+}
+
+/// Iterator of `(pass, item)` pairs, see `Looper::passes`.
+pub struct Passes<T> {
+    looper: Looper<T>,
+}
+
+impl<T> Iterator for Passes<T>
+where
+    T: Iterator + Clone
+{
+    type Item = (usize, <T as Iterator>::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.looper.next()?;
+        Some((self.looper.pass(), item))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.looper.size_hint()
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_looper() {
+        let looped = looper([1, 2, 3].iter()).take(7).cloned().collect::<Vec<_>>();
+        assert_eq!(looped, vec![1, 2, 3, 1, 2, 3, 1]);
+
+        let mut single = looper(Some(5).into_iter());
+        assert_eq!((single.next(), single.next(), single.pass()), (Some(5), Some(5), 1));
+    }
+
+    #[test]
+    fn test_empty() {
+        let mut empty = looper([0u8; 0].iter());
+        assert_eq!(empty.next(), None);
+        assert_eq!(empty.next(), None);
+        assert_eq!(empty.pass(), 0);
+        assert_eq!(empty.size_hint(), (0, Some(0)));
+    }
+
+    #[test]
+    fn test_size_hint() {
+        assert_eq!(looper([1, 2].iter()).size_hint(), (usize::MAX, None));
+        assert_eq!(looper([1, 2].iter().filter(|&&n| n > 1)).size_hint(), (0, None));
+        assert_eq!(looper([1].iter().filter(|&&n| n > 1)).next(), None);
+    }
+
+    #[test]
+    fn test_passes() {
+        let passes = looper("ab".chars()).passes().take(5).collect::<Vec<_>>();
+        assert_eq!(passes, vec![(0, 'a'), (0, 'b'), (1, 'a'), (1, 'b'), (2, 'a')]);
+    }
+}
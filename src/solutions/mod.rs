@@ -1,11 +1,138 @@
+use std::fmt;
+
+pub mod answers;
+pub mod circle;
+pub mod day;
+pub mod device;
+pub mod error;
+pub mod iter_utils;
+pub mod ocr;
+pub mod profiles;
 pub mod utils;
 
+// The template for new days is compiled along with the tests so that it
+// keeps up with the `Day` interface.
+#[cfg(test)]
+#[path = "../template.rs"]
+#[allow(dead_code, unused_imports)]
+mod template;
+
 pub mod advent1;
 pub mod advent2;
 pub mod advent3;
 pub mod advent4;
 pub mod advent5;
 pub mod advent6;
-//pub mod advent7;
+pub mod advent7;
 pub mod advent8;
 pub mod advent9;
+pub mod advent10;
+pub mod advent11;
+pub mod advent12;
+pub mod advent13;
+pub mod advent14;
+pub mod advent15;
+pub mod advent16;
+pub mod advent17;
+pub mod advent18;
+pub mod advent19;
+pub mod advent20;
+pub mod advent21;
+pub mod advent22;
+pub mod advent23;
+pub mod advent24;
+pub mod advent25;
+
+pub use self::day::{Answers, Day, Outcome, Part, Timings};
+pub use self::error::Error;
+
+/// The last day of the advent calendar.
+pub const LAST_DAY: u32 = 25;
+
+/// A solved day: where its input lives and how to run it.
+pub struct DaySolver {
+    pub day: u32,
+    /// Input file of the day, `None` if the input is part of the solution.
+    pub input: Option<&'static str>,
+    pub run: day::Runner,
+}
+
+/// All days that are currently solved, in calendar order.
+pub static DAYS: &[DaySolver] = &[
+    DaySolver { day: 1, input: Some(advent1::INPUT), run: day::run::<advent1::Day1> },
+    DaySolver { day: 2, input: Some(advent2::INPUT), run: day::run::<advent2::Day2> },
+    DaySolver { day: 3, input: Some(advent3::INPUT), run: day::run::<advent3::Day3> },
+    DaySolver { day: 4, input: Some(advent4::INPUT), run: day::run::<advent4::Day4> },
+    DaySolver { day: 5, input: Some(advent5::INPUT), run: day::run::<advent5::Day5> },
+    DaySolver { day: 6, input: Some(advent6::INPUT), run: day::run::<advent6::Day6> },
+    DaySolver { day: 7, input: Some(advent7::INPUT), run: day::run::<advent7::Day7> },
+    DaySolver { day: 8, input: Some(advent8::INPUT), run: day::run::<advent8::Day8> },
+    DaySolver { day: 9, input: Some(advent9::INPUT), run: day::run::<advent9::Day9> },
+    DaySolver { day: 10, input: Some(advent10::INPUT), run: day::run::<advent10::Day10> },
+    DaySolver { day: 11, input: Some(advent11::INPUT), run: day::run::<advent11::Day11> },
+    DaySolver { day: 12, input: Some(advent12::INPUT), run: day::run::<advent12::Day12> },
+    DaySolver { day: 13, input: Some(advent13::INPUT), run: day::run::<advent13::Day13> },
+    DaySolver { day: 14, input: Some(advent14::INPUT), run: day::run::<advent14::Day14> },
+    DaySolver { day: 15, input: Some(advent15::INPUT), run: day::run::<advent15::Day15> },
+    DaySolver { day: 16, input: Some(advent16::INPUT), run: day::run::<advent16::Day16> },
+    DaySolver { day: 17, input: Some(advent17::INPUT), run: day::run::<advent17::Day17> },
+    DaySolver { day: 18, input: Some(advent18::INPUT), run: day::run::<advent18::Day18> },
+    DaySolver { day: 19, input: Some(advent19::INPUT), run: day::run::<advent19::Day19> },
+    DaySolver { day: 20, input: Some(advent20::INPUT), run: day::run::<advent20::Day20> },
+    DaySolver { day: 21, input: Some(advent21::INPUT), run: day::run::<advent21::Day21> },
+    DaySolver { day: 22, input: Some(advent22::INPUT), run: day::run::<advent22::Day22> },
+    DaySolver { day: 23, input: Some(advent23::INPUT), run: day::run::<advent23::Day23> },
+    DaySolver { day: 24, input: Some(advent24::INPUT), run: day::run::<advent24::Day24> },
+    DaySolver { day: 25, input: Some(advent25::INPUT), run: day::run::<advent25::Day25> },
+];
+
+#[derive(Debug, PartialEq)]
+pub enum LookupError {
+    /// The day is not part of the calendar at all.
+    UnknownDay(u32),
+    /// The day is part of the calendar but has no solution yet.
+    NotImplemented(u32),
+}
+
+impl fmt::Display for LookupError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LookupError::UnknownDay(day) =>
+                write!(f, "day {} does not exist, pick a day from 1 to {}", day, LAST_DAY),
+            LookupError::NotImplemented(day) =>
+                write!(f, "day {} is not implemented yet", day),
+        }
+    }
+}
+
+/// Look up the registered solver for a day.
+pub fn find_day(day: u32) -> Result<&'static DaySolver, LookupError> {
+    if !(1..=LAST_DAY).contains(&day) {
+        return Err(LookupError::UnknownDay(day));
+    }
+
+    DAYS.iter()
+        .find(|solver| solver.day == day)
+        .ok_or(LookupError::NotImplemented(day))
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_find_day() {
+        assert_eq!(find_day(3).map(|solver| solver.day), Ok(3));
+        assert_eq!(find_day(25).map(|solver| solver.day), Ok(25));
+        assert_eq!(find_day(0).map(|solver| solver.day), Err(LookupError::UnknownDay(0)));
+        assert_eq!(find_day(26).map(|solver| solver.day), Err(LookupError::UnknownDay(26)));
+    }
+
+    #[test]
+    fn test_days_sorted() {
+        for pair in DAYS.windows(2) {
+            assert!(pair[0].day < pair[1].day);
+        }
+    }
+}
@@ -0,0 +1,176 @@
+//! Reading the block letters that some puzzles draw as their answer.
+//!
+//! Only the large 6×10 AoC font is supported. The 6×8 font has no known
+//! complete glyph set here, so 8 pixel high pictures are reported as having
+//! no font rather than being read from a partial one.
+
+use solutions::error::{Error, Result};
+
+/// A letter of a font, drawn with '#' (lit) and '.' (dark) rows.
+struct Glyph {
+    letter: char,
+    rows: &'static [&'static str],
+}
+
+/// The large AoC font, 6 pixels wide and 10 pixels high (e.g. 2018 day 10).
+static FONT_10: &[Glyph] = &[
+    Glyph { letter: 'A', rows: &["..##..", ".#..#.", "#....#", "#....#", "#....#",
+                                 "######", "#....#", "#....#", "#....#", "#....#"] },
+    Glyph { letter: 'B', rows: &["#####.", "#....#", "#....#", "#....#", "#####.",
+                                 "#....#", "#....#", "#....#", "#....#", "#####."] },
+    Glyph { letter: 'C', rows: &[".####.", "#....#", "#.....", "#.....", "#.....",
+                                 "#.....", "#.....", "#.....", "#....#", ".####."] },
+    Glyph { letter: 'E', rows: &["######", "#.....", "#.....", "#.....", "#####.",
+                                 "#.....", "#.....", "#.....", "#.....", "######"] },
+    Glyph { letter: 'F', rows: &["######", "#.....", "#.....", "#.....", "#####.",
+                                 "#.....", "#.....", "#.....", "#.....", "#....."] },
+    Glyph { letter: 'G', rows: &[".####.", "#....#", "#.....", "#.....", "#.....",
+                                 "#..###", "#....#", "#....#", "#...##", ".###.#"] },
+    Glyph { letter: 'H', rows: &["#....#", "#....#", "#....#", "#....#", "######",
+                                 "#....#", "#....#", "#....#", "#....#", "#....#"] },
+    Glyph { letter: 'J', rows: &["...###", "....#.", "....#.", "....#.", "....#.",
+                                 "....#.", "....#.", "#...#.", "#...#.", ".###.."] },
+    Glyph { letter: 'K', rows: &["#....#", "#...#.", "#..#..", "#.#...", "##....",
+                                 "##....", "#.#...", "#..#..", "#...#.", "#....#"] },
+    Glyph { letter: 'L', rows: &["#.....", "#.....", "#.....", "#.....", "#.....",
+                                 "#.....", "#.....", "#.....", "#.....", "######"] },
+    Glyph { letter: 'N', rows: &["#....#", "##...#", "##...#", "#.#..#", "#.#..#",
+                                 "#..#.#", "#..#.#", "#...##", "#...##", "#....#"] },
+    Glyph { letter: 'P', rows: &["#####.", "#....#", "#....#", "#....#", "#####.",
+                                 "#.....", "#.....", "#.....", "#.....", "#....."] },
+    Glyph { letter: 'R', rows: &["#####.", "#....#", "#....#", "#....#", "#####.",
+                                 "#..#..", "#...#.", "#...#.", "#....#", "#....#"] },
+    Glyph { letter: 'X', rows: &["#....#", "#....#", ".#..#.", ".#..#.", "..##..",
+                                 "..##..", ".#..#.", ".#..#.", "#....#", "#....#"] },
+    Glyph { letter: 'Z', rows: &["######", ".....#", ".....#", "....#.", "...#..",
+                                 "..#...", ".#....", "#.....", "#.....", "######"] },
+];
+
+fn font_for_height(height: usize) -> Option<&'static [Glyph]> {
+    match height {
+        10 => Some(FONT_10),
+        _ => None,
+    }
+}
+
+fn draw(grid: &[Vec<bool>], columns: (usize, usize)) -> Vec<String> {
+    grid.iter()
+        .map(|row| row[columns.0..columns.1].iter()
+            .map(|&lit| if lit { '#' } else { '.' })
+            .collect())
+        .collect()
+}
+
+/// Read the block letters from a grid of lit pixels (`grid[y][x]`), for puzzles
+/// that draw their answer. The lit pixels are split into letters at empty columns
+/// and each letter is looked up in the font with the same height.
+/// Empty rows and columns around the letters are ignored.
+pub fn recognize(grid: &[Vec<bool>]) -> Result<String> {
+    let width = grid.iter().map(|row| row.len()).max().unwrap_or(0);
+
+    // crop the grid to the rows with lit pixels, padding short rows
+    let top = grid.iter().position(|row| row.contains(&true));
+    let bottom = grid.iter().rposition(|row| row.contains(&true));
+    let (top, bottom) = match (top, bottom) {
+        (Some(top), Some(bottom)) => (top, bottom + 1),
+        _ => return Err(Error::no_solution("there are no letters in the picture")),
+    };
+
+    let cropped = grid[top..bottom].iter()
+        .map(|row| (0..width).map(|x| row.get(x).cloned().unwrap_or(false)).collect())
+        .collect::<Vec<Vec<bool>>>();
+
+    let height = cropped.len();
+    let font = font_for_height(height).ok_or_else(|| {
+        Error::no_solution(format!("no font for letters that are {} pixels high", height))
+    })?;
+
+    // every run of columns with lit pixels is one letter
+    let lit_column = |x: usize| cropped.iter().any(|row| row[x]);
+    let mut letters = String::new();
+    let mut x = 0;
+    while x < width {
+        if !lit_column(x) {
+            x += 1;
+            continue;
+        }
+
+        let start = x;
+        while x < width && lit_column(x) {
+            x += 1;
+        }
+
+        let rows = draw(&cropped, (start, x));
+        let glyph = font.iter()
+            .find(|glyph| glyph.rows.iter().eq(rows.iter()))
+            .ok_or_else(|| Error::no_solution(format!("unknown letter at column {}:\n{}",
+                                                      start, rows.join("\n"))))?;
+        letters.push(glyph.letter);
+    }
+
+    Ok(letters)
+}
+
+/// Convenience wrapper for pictures drawn with '#' (lit) and any other character.
+pub fn recognize_str(picture: &str) -> Result<String> {
+    let grid = picture.lines()
+        .map(|line| line.trim().chars().map(|c| c == '#').collect())
+        .collect::<Vec<Vec<bool>>>();
+
+    recognize(&grid)
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_font_shapes() {
+        for glyph in FONT_10.iter() {
+            assert_eq!(glyph.rows.len(), 10, "height of '{}'", glyph.letter);
+            assert!(glyph.rows.iter().all(|row| row.len() == 6), "width of '{}'", glyph.letter);
+        }
+    }
+
+    #[test]
+    fn test_every_letter() {
+        // all letters of the large font next to each other, with two empty columns in between
+        let picture = (0..10)
+            .map(|y| FONT_10.iter().map(|glyph| glyph.rows[y]).collect::<Vec<_>>().join(".."))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        assert_eq!(recognize_str(&picture).unwrap(), "ABCEFGHJKLNPRXZ");
+    }
+
+    #[test]
+    fn test_small_font() {
+        // 8 pixel high letters are not supported
+        let picture = "\
+            #...#.###\n\
+            #...#..#.\n\
+            #...#..#.\n\
+            #####..#.\n\
+            #...#..#.\n\
+            #...#..#.\n\
+            #...#..#.\n\
+            #...#.###";
+
+        let err = recognize_str(picture).unwrap_err();
+        assert_eq!(err.to_string(), "no solution: no font for letters that are 8 pixels high");
+    }
+
+    #[test]
+    fn test_unknown_letters() {
+        let err = recognize_str("###\n#.#\n###").unwrap_err();
+        assert_eq!(err.to_string(), "no solution: no font for letters that are 3 pixels high");
+
+        let err = recognize_str("").unwrap_err();
+        assert_eq!(err.to_string(), "no solution: there are no letters in the picture");
+
+        let picture = "#....#\n".repeat(10);
+        let err = recognize_str(&picture).unwrap_err();
+        assert!(err.to_string().starts_with("no solution: unknown letter at column 0"));
+    }
+}
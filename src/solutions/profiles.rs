@@ -0,0 +1,108 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use solutions::answers::{parse_answers, ANSWERS_FILE};
+use solutions::day::Answers;
+use solutions::error::{Error, Result};
+use solutions::utils::{self, InputSource};
+
+/// The inputs directory itself is the profile with this name.
+pub static DEFAULT_PROFILE: &str = "default";
+
+/// A named set of puzzle inputs (one person's account) with the accepted
+/// answers for them, e.g. `data/alice/inputN` and `data/alice/answers.toml`.
+#[derive(Debug, PartialEq)]
+pub struct Profile {
+    pub name: String,
+    pub dir: PathBuf,
+}
+
+impl Profile {
+    pub fn source(&self) -> InputSource {
+        InputSource::Dir(self.dir.clone())
+    }
+
+    /// Whether the profile has an input file with this name.
+    pub fn has_input(&self, file_name: &str) -> bool {
+        self.dir.join(file_name).is_file()
+    }
+
+    /// The accepted answers of the profile, empty if it has no answers file.
+    pub fn answers(&self) -> Result<BTreeMap<u32, Answers>> {
+        let path = self.dir.join(ANSWERS_FILE);
+        if !path.exists() {
+            return Ok(BTreeMap::new());
+        }
+
+        parse_answers(&utils::file_to_string(&path.to_string_lossy())?)
+            .map_err(|err| match err {
+                Error::Parse { line, message } =>
+                    Error::parse(format!("{}{}: {}", path.display(),
+                                         line.map_or(String::new(), |line| format!(":{}", line)), message)),
+                other => other,
+            })
+    }
+}
+
+/// All profiles in the inputs directory: the directory itself and every
+/// subdirectory with an answers file, sorted by name after the default.
+pub fn list_profiles(root: &Path) -> Result<Vec<Profile>> {
+    let entries = fs::read_dir(root)
+        .map_err(|err| Error::Io(format!("'{}'", root.display()), err))?;
+
+    let mut profiles = Vec::new();
+    for entry in entries {
+        let entry = entry.map_err(|err| Error::Io(format!("'{}'", root.display()), err))?;
+        let dir = entry.path();
+        if dir.is_dir() && dir.join(ANSWERS_FILE).is_file() {
+            profiles.push(Profile { name: entry.file_name().to_string_lossy().into_owned(), dir });
+        }
+    }
+    profiles.sort_by(|a, b| a.name.cmp(&b.name));
+
+    profiles.insert(0, Profile { name: DEFAULT_PROFILE.to_owned(), dir: root.to_owned() });
+    Ok(profiles)
+}
+
+/// Look up a profile by name.
+pub fn find_profile(root: &Path, name: &str) -> Result<Profile> {
+    list_profiles(root)?
+        .into_iter()
+        .find(|profile| profile.name == name)
+        .ok_or_else(|| Error::Io(format!("profile '{}'", name),
+                                 io::Error::new(io::ErrorKind::NotFound,
+                                                format!("no directory with an {} in '{}'",
+                                                        ANSWERS_FILE, root.display()))))
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn test_list_profiles() {
+        let root = env::temp_dir().join(format!("aoc_2018_profiles_{}", std::process::id()));
+        fs::create_dir_all(root.join("bob")).unwrap();
+        fs::create_dir_all(root.join("alice")).unwrap();
+        fs::create_dir_all(root.join("samples")).unwrap();
+        fs::write(root.join("bob").join(ANSWERS_FILE), "[day1]\npart1 = \"3\"\n").unwrap();
+        fs::write(root.join("alice").join(ANSWERS_FILE), "").unwrap();
+        fs::write(root.join("bob").join("input1"), "+1\n+2\n").unwrap();
+
+        let names: Vec<String> = list_profiles(&root).unwrap().into_iter().map(|profile| profile.name).collect();
+        assert_eq!(names, vec!["default", "alice", "bob"]);
+
+        let bob = find_profile(&root, "bob").unwrap();
+        assert!(bob.has_input("input1"));
+        assert!(!bob.has_input("input2"));
+        assert_eq!(bob.answers().unwrap()[&1].part1, Some("3".to_owned()));
+        assert!(find_profile(&root, DEFAULT_PROFILE).unwrap().answers().unwrap().is_empty());
+        assert!(find_profile(&root, "samples").is_err());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
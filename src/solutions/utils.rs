@@ -1,10 +1,166 @@
+use std::env;
+use std::fmt::Display;
 use std::fs::File;
+use std::io;
 use std::io::prelude::*;
+use std::io::BufReader;
+use std::path::PathBuf;
+use std::str::FromStr;
 
-pub fn file_to_string(file_path: &str) -> String {
-    let mut file = File::open(file_path).expect("Input data not found");
+use solutions::error::{Error, Result};
+
+/// Directory with the puzzle inputs if nothing else is configured.
+pub static DEFAULT_INPUTS_DIR: &str = "data";
+
+/// Environment variable that overrides the inputs directory.
+pub static INPUTS_DIR_VAR: &str = "AOC_INPUTS_DIR";
+
+pub fn file_to_string(file_path: &str) -> Result<String> {
     let mut file_contents = String::new();
-    file.read_to_string(&mut file_contents).unwrap();
+    File::open(file_path)
+        .and_then(|mut file| file.read_to_string(&mut file_contents))
+        .map_err(|err| Error::Io(format!("'{}'", file_path), err))?;
+
+    Ok(file_contents)
+}
+
+/// Parse a single number, with an error message that names the bad field.
+pub fn parse_num<T>(field: &str) -> Result<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    field.trim().parse::<T>()
+        .map_err(|err| Error::parse(format!("invalid number '{}': {}", field.trim(), err)))
+}
+
+/// Parse every line of the input, attaching line numbers to parse errors.
+pub fn parse_lines<T, F>(input: &str, mut parse_line: F) -> Result<Vec<T>>
+where
+    F: FnMut(&str) -> Result<T>,
+{
+    input.lines()
+        .enumerate()
+        .map(|(num, line)| parse_line(line).map_err(|err| err.at_line(num + 1)))
+        .collect()
+}
+
+/// The lines of a reader, read one at a time. Line endings are stripped and
+/// read errors are reported as `Error::Io`.
+pub fn read_lines<R: BufRead>(reader: R) -> impl Iterator<Item = Result<String>> {
+    reader.lines().map(|line| line.map_err(|err| Error::Io(String::from("input"), err)))
+}
+
+/// Parse every line read from `reader`, like `parse_lines` but without
+/// holding the whole input in memory.
+pub fn parse_reader_lines<T, R, F>(reader: R, mut parse_line: F) -> Result<Vec<T>>
+where
+    R: BufRead,
+    F: FnMut(&str) -> Result<T>,
+{
+    read_lines(reader)
+        .enumerate()
+        .map(|(num, line)| line.and_then(|line| parse_line(&line)).map_err(|err| err.at_line(num + 1)))
+        .collect()
+}
+
+/// The inputs directory from `AOC_INPUTS_DIR`, or `data` if it is not set.
+pub fn inputs_dir() -> PathBuf {
+    env::var_os(INPUTS_DIR_VAR)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_INPUTS_DIR))
+}
+
+/// Where the puzzle input of a day is read from.
+#[derive(Debug, PartialEq, Clone)]
+pub enum InputSource {
+    /// A single input file, used as is.
+    File(PathBuf),
+    /// The input is piped in on stdin.
+    Stdin,
+    /// A directory holding the input files of all days (e.g. `data/input3`).
+    Dir(PathBuf),
+}
+
+impl InputSource {
+    /// The inputs directory from `AOC_INPUTS_DIR`, or `data` if it is not set.
+    pub fn from_env() -> InputSource {
+        InputSource::Dir(inputs_dir())
+    }
+
+    /// Read the input text. `file_name` is the name of the day's input
+    /// file and only used when reading from an inputs directory.
+    pub fn read(&self, file_name: &str) -> Result<String> {
+        match self {
+            InputSource::File(path) => file_to_string(&path.to_string_lossy()),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)
+                    .map_err(|err| Error::Io(String::from("stdin"), err))?;
+                Ok(input)
+            }
+            InputSource::Dir(dir) => file_to_string(&dir.join(file_name).to_string_lossy()),
+        }
+    }
+
+    /// Whether the input of a day exists. Only an inputs directory can
+    /// lack the input file of a day.
+    pub fn has_input(&self, file_name: &str) -> bool {
+        match self {
+            InputSource::Dir(dir) => dir.join(file_name).is_file(),
+            _ => true,
+        }
+    }
+
+    /// Open the input for reading it while it is parsed, see `read`.
+    pub fn open(&self, file_name: &str) -> Result<Box<dyn BufRead>> {
+        let path = match self {
+            InputSource::File(path) => path.clone(),
+            InputSource::Stdin => return Ok(Box::new(io::stdin().lock())),
+            InputSource::Dir(dir) => dir.join(file_name),
+        };
+
+        let file = File::open(&path).map_err(|err| Error::Io(format!("'{}'", path.display()), err))?;
+        Ok(Box::new(BufReader::new(file)))
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_lines() {
+        let nums: Result<Vec<i32>> = parse_lines("1\n-2\n3", parse_num);
+        assert_eq!(nums.unwrap(), vec![1, -2, 3]);
+
+        let err = parse_lines::<i32, _>("1\nx\n3", parse_num).unwrap_err();
+        assert_eq!(err.to_string(), "line 2: invalid number 'x': invalid digit found in string");
+    }
+
+    #[test]
+    fn test_parse_reader_lines() {
+        let nums: Vec<i32> = parse_reader_lines("1\n-2\r\n3\n".as_bytes(), parse_num).unwrap();
+        assert_eq!(nums, vec![1, -2, 3]);
+
+        let err = parse_reader_lines::<i32, _, _>("1\nx\n3".as_bytes(), parse_num).unwrap_err();
+        assert_eq!(err.to_string(), "line 2: invalid number 'x': invalid digit found in string");
+    }
+
+    #[test]
+    fn test_missing_file() {
+        let source = InputSource::Dir(PathBuf::from("does/not/exist"));
+        assert!(!source.has_input("input1"));
+        match source.read("input1") {
+            Err(Error::Io(path, _)) => assert!(path.contains("input1")),
+            other => panic!("expected an I/O error, got {:?}", other),
+        }
 
-    file_contents
+        match source.open("input1") {
+            Err(Error::Io(path, _)) => assert!(path.contains("input1")),
+            Err(other) => panic!("expected an I/O error, got {:?}", other),
+            Ok(_) => panic!("expected an I/O error"),
+        }
+    }
 }
@@ -1,6 +1,7 @@
-use solutions::utils;
+use solutions::day::Day;
+use solutions::error::Result;
 
-static INPUT: &str = "data/input1";
+pub static INPUT: &str = "input1";
 
 // Problem 1
 // ==================================================
@@ -11,24 +12,43 @@ static INPUT: &str = "data/input1";
 // Interface
 // ==================================================
 
-pub fn solution1() -> () {
+pub struct Day1;
 
-}
+impl Day for Day1 {
+    type Input = String;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<String> {
+        Ok(input.to_owned())
+    }
+
+    fn part1(_input: &String) -> Result<usize> {
+        Ok(0)
+    }
 
+    fn part2(_input: &String) -> Result<usize> {
+        Ok(0)
+    }
+}
 
-pub fn solution2() -> () {
 
+#[cfg(test)]
+fn solution1(input: &str) -> Result<usize> {
+    let input = Day1::parse(input)?;
+    Day1::part1(&input)
 }
 
 
-pub fn solve_day() {
-    solution1();
-    solution2();
+#[cfg(test)]
+fn solution2(input: &str) -> Result<usize> {
+    let input = Day1::parse(input)?;
+    Day1::part2(&input)
 }
 
 
 // Test the sample puzzle inputs
-// ================================================== 
+// ==================================================
 #[cfg(test)]
 mod test {
     use super::*;
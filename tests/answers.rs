@@ -0,0 +1,66 @@
+//! Runs every registered day on the checked-in inputs of every profile and
+//! compares the results with the profile's accepted answers, e.g. the inputs
+//! in `data` with `data/answers.toml`.
+
+extern crate solutions_lib;
+
+use std::path::Path;
+
+use solutions_lib::answers::compare;
+use solutions_lib::profiles::{list_profiles, Profile};
+use solutions_lib::DAYS;
+
+fn check_profile(profile: &Profile, failures: &mut Vec<String>) {
+    let accepted = profile.answers().unwrap();
+    let source = profile.source();
+
+    for &day in accepted.keys().filter(|day| !DAYS.iter().any(|solver| solver.day == **day)) {
+        failures.push(format!("{}: day {} has answers but is not registered", profile.name, day));
+    }
+
+    for solver in DAYS {
+        let has_input = solver.input.is_none_or(|file_name| profile.has_input(file_name));
+        let expected = match accepted.get(&solver.day) {
+            Some(expected) => expected,
+            None if has_input => {
+                failures.push(format!("{}: day {} has no accepted answers for its input",
+                                      profile.name, solver.day));
+                continue;
+            }
+            None => continue,
+        };
+
+        let input = match solver.input.map(|file_name| source.read(file_name)) {
+            Some(Ok(input)) => input,
+            Some(Err(err)) => {
+                failures.push(format!("{}: day {}: {}", profile.name, solver.day, err));
+                continue;
+            }
+            None => String::new(),
+        };
+
+        match (solver.run)(&mut input.as_bytes(), None) {
+            Ok((outcome, _)) => {
+                for (part, err) in outcome.errors() {
+                    failures.push(format!("{}: day {} part {}: {}", profile.name, solver.day, part, err));
+                }
+                failures.extend(compare(solver.day, expected, &outcome.answers())
+                    .into_iter()
+                    .map(|disagreement| format!("{}: {}", profile.name, disagreement)));
+            }
+            Err(err) => failures.push(format!("{}: day {}: {}", profile.name, solver.day, err)),
+        }
+    }
+}
+
+#[test]
+fn test_known_answers() {
+    let data = Path::new(env!("CARGO_MANIFEST_DIR")).join("data");
+
+    let mut failures = Vec::new();
+    for profile in list_profiles(&data).unwrap() {
+        check_profile(&profile, &mut failures);
+    }
+
+    assert!(failures.is_empty(), "answers changed:\n{}", failures.join("\n"));
+}